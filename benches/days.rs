@@ -0,0 +1,189 @@
+// Times parse and both parts for each day, on its example and on the local input if there is one.
+//
+//     cargo bench --bench days -- [days] [--save-baseline NAME] [--baseline NAME] [--inputs DIR] [--template NAME]
+//
+// Baselines are kept in target/bench-baselines, so a branch can be compared against main with
+// `cargo bench --bench days -- --save-baseline main` on main, then `--baseline main` on the branch.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use aoc_2022::input::{self, Source};
+use aoc_2022::puzzles;
+use aoc_2022::runner;
+use aoc_2022::solver::{Puzzle, Timings};
+
+const BUDGET: Duration = Duration::from_millis(500);
+const MAX_SAMPLES: usize = 100;
+
+type Key = (u8, &'static str);
+type Baseline = HashMap<Key, [Duration; 4]>;
+
+// Repeats the whole solve until the time budget runs out, keeping the median of each phase
+fn measure(solver: &dyn Puzzle, input: &str) -> Result<Timings, String> {
+    let mut samples: Vec<Timings> = Vec::new();
+    let start = Instant::now();
+    while samples.is_empty() || (samples.len() < MAX_SAMPLES && start.elapsed() < BUDGET) {
+        samples.push(solver.solve(input).map_err(|e| e.to_string())?.timings);
+    }
+
+    let median = |phase: fn(&Timings) -> Duration| {
+        let mut times: Vec<Duration> = samples.iter().map(phase).collect();
+        times.sort();
+        times[times.len() / 2]
+    };
+    Ok(Timings { parse: median(|t| t.parse), part1: median(|t| t.part1), part2: median(|t| t.part2) })
+}
+
+fn phases(t: &Timings) -> [Duration; 4] {
+    [t.parse, t.part1, t.part2, t.total()]
+}
+
+fn baseline_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target").join("bench-baselines").join(format!("{name}.tsv"))
+}
+
+fn save_baseline(name: &str, results: &[(Key, Timings)]) -> Result<(), String> {
+    let path = baseline_path(name);
+    let lines: Vec<String> = results.iter()
+        .map(|((day, kind), t)| {
+            let nanos: Vec<String> = phases(t).iter().map(|d| d.as_nanos().to_string()).collect();
+            format!("{day}\t{kind}\t{}", nanos.join("\t"))
+        })
+        .collect();
+
+    fs::create_dir_all(path.parent().expect("Baselines live in a directory"))
+        .and_then(|_| fs::write(&path, lines.join("\n") + "\n"))
+        .map_err(|e| format!("could not save {}: {e}", path.display()))
+}
+
+fn load_baseline(name: &str) -> Result<Baseline, String> {
+    let path = baseline_path(name);
+    let text = fs::read_to_string(&path).map_err(|e| format!("could not read {}: {e}", path.display()))?;
+    let mut baseline = Baseline::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let bad = || format!("{} line {}: expected day, input and four timings", path.display(), i + 1);
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [day, kind, rest @ ..] = &fields[..] else { return Err(bad()) };
+        let kind = match *kind {
+            "example" => "example",
+            "input" => "input",
+            _ => return Err(bad())
+        };
+        let nanos: Vec<u64> = rest.iter().map(|n| n.parse::<u64>()).collect::<Result<_, _>>().map_err(|_| bad())?;
+        let times: [Duration; 4] = nanos.into_iter().map(Duration::from_nanos).collect::<Vec<Duration>>()
+            .try_into().map_err(|_| bad())?;
+        baseline.insert((day.parse().map_err(|_| bad())?, kind), times);
+    }
+    Ok(baseline)
+}
+
+fn cell(time: Duration, before: Option<Duration>) -> String {
+    match before {
+        Some(b) if !b.is_zero() => {
+            let change = (time.as_secs_f64() / b.as_secs_f64() - 1.0) * 100.0;
+            format!("{time:.2?} ({change:+.1}%)")
+        },
+        _ => format!("{time:.2?}")
+    }
+}
+
+fn main() -> ExitCode {
+    let mut spec = "all".to_string();
+    let mut save: Option<String> = None;
+    let mut compare: Option<String> = None;
+    let mut dir = PathBuf::from(".");
+    let mut template = input::DEFAULT_TEMPLATE.to_string();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        let parsed = match arg.as_str() {
+            // cargo passes this to every bench target
+            "--bench" => Ok(()),
+            "--save-baseline" => value().map(|v| save = Some(v)),
+            "--baseline" => value().map(|v| compare = Some(v)),
+            "--inputs" => value().map(|v| dir = PathBuf::from(v)),
+            "--template" => value().map(|v| template = v),
+            flag if flag.starts_with("--") => Err(format!("unknown option {flag}")),
+            _ => {
+                spec = arg.clone();
+                Ok(())
+            }
+        };
+        if let Err(e) = parsed {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let baseline = match compare.as_deref().map(load_baseline).transpose() {
+        Ok(b) => b.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let days = puzzles::registry();
+    let selected = match runner::select(&days, &spec) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let source = Source::Directory { dir, template };
+
+    let mut results: Vec<(Key, Timings)> = Vec::new();
+    let mut failed = false;
+    println!("{:>3}  {:<7}  {:>20}  {:>20}  {:>20}  {:>20}", "Day", "Input", "Parse", "Part 1", "Part 2", "Total");
+
+    for day in selected {
+        let example = &day.example;
+        let real = source.read(day.number);
+        let cases: [(&str, &dyn Puzzle, Result<&str, String>); 2] = [
+            ("example", example.solver.as_ref(), Ok(example.input)),
+            ("input", day.solver.as_ref(), real.as_deref().map_err(|e| e.to_string()))
+        ];
+
+        for (kind, solver, text) in cases {
+            let key = (day.number, kind);
+            let text = match text {
+                Ok(t) => t,
+                Err(e) => {
+                    println!("{:>3}  {kind:<7}  skipped: {e}", day.number);
+                    continue;
+                }
+            };
+            match measure(solver, text) {
+                Ok(timings) => {
+                    let before = baseline.get(&key);
+                    let cells: Vec<String> = phases(&timings).iter().enumerate()
+                        .map(|(i, t)| format!("{:>20}", cell(*t, before.map(|b| b[i]))))
+                        .collect();
+                    println!("{:>3}  {kind:<7}  {}", day.number, cells.join("  "));
+                    results.push((key, timings));
+                },
+                Err(e) => {
+                    failed = true;
+                    println!("{:>3}  {kind:<7}  failed: {e}", day.number);
+                }
+            }
+        }
+    }
+
+    if let Some(name) = save {
+        if let Err(e) = save_baseline(&name, &results) {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+        println!("\nSaved baseline {name}");
+    }
+
+    if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+}
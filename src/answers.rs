@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::fmt;
+use crate::puzzles::Day;
+use crate::solver::Answer;
+
+// Expected answers keyed by (day, part), read from a small subset of TOML:
+//
+//     [day1]
+//     part1 = 24000
+//     part2 = "CMZ"
+//
+// Multi-line strings in triple quotes are read as images.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    expected: HashMap<(u8, u8), Answer>
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail { expected: Answer },
+    Missing
+}
+
+impl Answers {
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.expected.get(&(day, part))
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: Answer) {
+        self.expected.insert((day, part), answer);
+    }
+
+    // What each day's built-in example should give
+    pub fn from_examples(days: &[&Day]) -> Self {
+        let mut answers = Answers::default();
+        for day in days {
+            answers.insert(day.number, 1, day.example.part1.clone());
+            answers.insert(day.number, 2, day.example.part2.clone());
+        }
+        answers
+    }
+
+    pub fn check(&self, day: u8, part: u8, actual: &Answer) -> Check {
+        match self.get(day, part) {
+            None => Check::Missing,
+            Some(expected) if same_answer(expected, actual) => Check::Pass,
+            Some(expected) => Check::Fail { expected: expected.clone() }
+        }
+    }
+}
+
+// Editors like to strip trailing spaces from pictures, so those don't count, and
+// dark pixels can be written as `.` to keep them visible
+fn same_answer(a: &Answer, b: &Answer) -> bool {
+    let row = |r: &String| r.replace('.', " ").trim_end().to_string();
+    match (a, b) {
+        (Answer::Image(x), Answer::Image(y)) => {
+            x.len() == y.len() && x.iter().zip(y).all(|(r, s)| row(r) == row(s))
+        },
+        _ => a == b
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AnswersError {
+    pub line: usize,
+    pub reason: String
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "answers line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+fn read_value(lines: &mut std::iter::Enumerate<std::str::Lines>, line: usize, value: &str) -> Result<Answer, AnswersError> {
+    let error = |reason: String| AnswersError { line, reason };
+
+    if let Some(rest) = value.strip_prefix("\"\"\"") {
+        // A newline straight after the opening quotes is not part of the string
+        let mut rows: Vec<String> = Vec::new();
+        if let Some(end) = rest.find("\"\"\"") {
+            return Ok(Answer::Text(rest[..end].to_string()));
+        }
+        if !rest.is_empty() {
+            rows.push(rest.to_string());
+        }
+        for (_, text) in lines.by_ref() {
+            if let Some(end) = text.find("\"\"\"") {
+                if end > 0 {
+                    rows.push(text[..end].to_string());
+                }
+                return Ok(Answer::Image(rows));
+            }
+            rows.push(text.to_string());
+        }
+        return Err(error("unterminated multi-line string".to_string()));
+    }
+
+    if let Some(rest) = value.strip_prefix('"') {
+        return match rest.strip_suffix('"') {
+            Some(text) => Ok(Answer::Text(text.to_string())),
+            None => Err(error(format!("unterminated string {value}")))
+        };
+    }
+
+    value.replace('_', "").parse::<i64>()
+        .map(Answer::Number)
+        .map_err(|_| error(format!("expected a number or string, found `{value}`")))
+}
+
+pub fn read_answers(input: &str) -> Result<Answers, AnswersError> {
+    let mut answers = Answers::default();
+    let mut day: Option<u8> = None;
+    let mut lines = input.lines().enumerate();
+
+    while let Some((i, raw)) = lines.next() {
+        let line = i + 1;
+        let text = raw.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        if let Some(section) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            let number = section.trim().strip_prefix("day").and_then(|n| n.parse::<u8>().ok())
+                .ok_or_else(|| AnswersError { line, reason: format!("expected a section like [day1], found [{section}]") })?;
+            day = Some(number);
+            continue;
+        }
+
+        let (key, value) = text.split_once('=')
+            .ok_or_else(|| AnswersError { line, reason: format!("expected `part1 = ...`, found `{text}`") })?;
+        let part = match key.trim() {
+            "part1" => 1,
+            "part2" => 2,
+            k => return Err(AnswersError { line, reason: format!("unknown key `{k}`, expected part1 or part2") })
+        };
+        let day = day.ok_or_else(|| AnswersError { line, reason: "answer outside of a [dayN] section".to_string() })?;
+        let answer = read_value(&mut lines, line, value.trim())?;
+        answers.insert(day, part, answer);
+    }
+
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reading_answers() {
+        let input = r#"
+# Checked against the website
+[day1]
+part1 = 24_000
+part2 = "45000"
+
+[day10]
+part2 = """
+##..
+.##.   """
+"#;
+        let answers = read_answers(input).unwrap();
+
+        assert_eq!(answers.get(1, 1), Some(&Answer::Number(24000)));
+        assert_eq!(answers.get(1, 2), Some(&Answer::Text("45000".to_string())));
+        assert_eq!(answers.get(10, 1), None);
+        assert_eq!(answers.get(10, 2), Some(&Answer::Image(vec!["##..".to_string(), ".##.   ".to_string()])));
+    }
+
+    #[test]
+    fn checking_answers() {
+        let answers = read_answers("[day10]\npart1 = 13140\npart2 = \"\"\"\n# #\n\"\"\"").unwrap();
+
+        assert_eq!(answers.check(10, 1, &Answer::Number(13140)), Check::Pass);
+        assert_eq!(answers.check(10, 1, &Answer::Number(1)), Check::Fail { expected: Answer::Number(13140) });
+        assert_eq!(answers.check(10, 2, &Answer::Image(vec!["# #   ".to_string()])), Check::Pass);
+        assert_eq!(answers.check(10, 2, &Answer::Image(vec!["#.#...".to_string()])), Check::Pass);
+        assert!(matches!(answers.check(10, 2, &Answer::Image(vec!["###".to_string()])), Check::Fail { .. }));
+        assert_eq!(answers.check(11, 1, &Answer::Number(1)), Check::Missing);
+    }
+
+    #[test]
+    fn bad_section() {
+        let error = read_answers("[day1]\npart1 = 5\n[week2]").unwrap_err();
+        assert_eq!(error.line, 3);
+    }
+}
@@ -0,0 +1,95 @@
+use std::{fmt, io};
+
+// Line and column are 1-based; a line of 0 means the problem is with the input as a whole
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String
+}
+
+impl ParseError {
+    pub fn new(day: u8, line: usize, column: usize, text: &str, reason: impl Into<String>) -> Self {
+        ParseError { day, line, column, text: text.to_string(), reason: reason.into() }
+    }
+
+    // Points the column at `token`, which should be a slice of `text`
+    pub fn at(day: u8, line: usize, text: &str, token: &str, reason: impl Into<String>) -> Self {
+        let start = text.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+        let column = if offset <= text.len() && text.is_char_boundary(offset) {
+            text[..offset].chars().count() + 1
+        } else {
+            1
+        };
+        ParseError::new(day, line, column, text, reason)
+    }
+
+    pub fn input(day: u8, reason: impl Into<String>) -> Self {
+        ParseError::new(day, 0, 0, "", reason)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line == 0 {
+            return write!(f, "day {}: {}", self.day, self.reason);
+        }
+        write!(f, "day {}, line {}, column {}: {}", self.day, self.line, self.column, self.reason)?;
+        write!(f, "\n  {}\n  {}^", self.text, " ".repeat(self.column.saturating_sub(1)))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug)]
+pub enum Error {
+    Io { path: String, source: io::Error },
+    Parse(ParseError)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "could not read {path}: {source}"),
+            Error::Parse(e) => write!(f, "{e}")
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse(e) => Some(e)
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_from_token() {
+        let text = "2-4,x-8";
+        let error = ParseError::at(4, 3, text, &text[4..5], "expected a number");
+        assert_eq!(error.column, 5);
+        assert_eq!(error.to_string(), "day 4, line 3, column 5: expected a number\n  2-4,x-8\n      ^");
+    }
+
+    #[test]
+    fn foreign_token_points_at_start() {
+        let token = String::from("x");
+        let error = ParseError::at(4, 1, "2-4,6-8", &token, "expected a number");
+        assert_eq!(error.column, 1);
+    }
+}
@@ -0,0 +1,85 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use crate::error::Error;
+
+pub const DEFAULT_TEMPLATE: &str = "in{day}.txt";
+
+// Where a day's puzzle input comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    Directory { dir: PathBuf, template: String }
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Source::Directory { dir: PathBuf::from("."), template: DEFAULT_TEMPLATE.to_string() }
+    }
+}
+
+// `{day}` is the plain day number, `{day:02}` pads it to two digits
+pub fn file_name(template: &str, day: u8) -> String {
+    template.replace("{day:02}", &format!("{day:02}")).replace("{day}", &day.to_string())
+}
+
+pub fn read_file(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|source| Error::Io { path: path.display().to_string(), source })
+}
+
+impl Source {
+    // "-" means stdin, anything else is a file
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path))
+        }
+    }
+
+    // Stdin and a single file can only feed one day
+    pub fn is_single(&self) -> bool {
+        !matches!(self, Source::Directory { .. })
+    }
+
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            Source::File(path) => Some(path.clone()),
+            Source::Stdin => None,
+            Source::Directory { dir, template } => Some(dir.join(file_name(template, day)))
+        }
+    }
+
+    pub fn read(&self, day: u8) -> Result<String, Error> {
+        match self.path(day) {
+            Some(path) => read_file(&path),
+            None => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text)
+                    .map_err(|source| Error::Io { path: "<stdin>".to_string(), source })?;
+                Ok(text)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_names() {
+        assert_eq!(file_name(DEFAULT_TEMPLATE, 7), "in7.txt");
+        assert_eq!(file_name("day{day:02}/input", 7), "day07/input");
+        assert_eq!(file_name("{day}-{day:02}.txt", 12), "12-12.txt");
+    }
+
+    #[test]
+    fn source_paths() {
+        let source = Source::Directory { dir: PathBuf::from("inputs/alice"), template: DEFAULT_TEMPLATE.to_string() };
+        assert_eq!(source.path(3), Some(PathBuf::from("inputs/alice/in3.txt")));
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(Source::from_arg("x.txt").path(3), Some(PathBuf::from("x.txt")));
+        assert!(!Source::default().is_single());
+    }
+}
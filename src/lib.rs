@@ -0,0 +1,13 @@
+//! Advent of Code 2022 solutions.
+//!
+//! Each module under [`puzzles`] exposes its typed parser (e.g. [`puzzles::valves::read_valves`]),
+//! `&str` convenience functions for each part, and a [`solver::Solver`] implementation.
+//! [`puzzles::registry`] lists every solved day.
+
+pub mod answers;
+pub mod error;
+pub mod input;
+mod parsing;
+pub mod puzzles;
+pub mod runner;
+pub mod solver;
@@ -1,136 +1,352 @@
-use std::{env, fs};
-mod puzzles;
-use puzzles::cleanup;
-use puzzles::food;
-use puzzles::rps;
-use puzzles::reorg;
-use puzzles::stacks;
-use puzzles::tuning;
-use puzzles::filewalk;
-use puzzles::trees;
-use puzzles::tail_follow;
-use puzzles::crt;
-use puzzles::monkeys;
-use puzzles::climbing;
-use puzzles::compare;
-use puzzles::sand;
-use puzzles::beacon;
-use puzzles::valves;
-
-fn read_file(filename: &str) -> String {
-    match fs::read_to_string(filename.to_string()) {
-        Ok(v) => v,
-        Err(_) => panic!("No filename {filename} found")
-    }
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use aoc_2022::answers::{self, Answers};
+use aoc_2022::input::{self, Source};
+use aoc_2022::puzzles::{self, food, reorg, rps, Day};
+use aoc_2022::runner;
+
+fn print_day(run: &runner::Run) -> Result<(), String> {
+    let solution = run.outcome.as_ref().map_err(|e| e.to_string())?;
+    println!("Question {} ({}): {}, {}", run.day.number, run.day.title, solution.part1, solution.part2);
+    println!("Took {:?}", solution.timings.total());
+    Ok(())
+}
+
+const USAGE: &str = "usage: aoc-2022 [verify] <day | all | first..=last> [--example] [--answers FILE]
+                [--input FILE | -] [--inputs DIR] [--template in{day}.txt] [--format text|json]
+       aoc-2022 food [--top K] [--percentile P]... [--at-least CALORIES] [--example] [--input FILE | -]
+       aoc-2022 guide [--target SCORE] [--example] [--input FILE | -]
+       aoc-2022 rucksacks [--example] [--input FILE | -]";
+
+#[derive(PartialEq)]
+enum Command {
+    Run,
+    Verify,
+    Food,
+    Guide,
+    Rucksacks
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+#[derive(PartialEq)]
+enum Format {
+    Text,
+    Json
+}
 
-    let value = match args.get(1) {
-        None => 0,
-        Some(n) => n.parse::<u8>().unwrap_or(0)
+struct Options {
+    command: Command,
+    format: Format,
+    days: String,
+    answers: String,
+    source: Source,
+    example: bool,
+    top: usize,
+    percentiles: Vec<f64>,
+    at_least: Option<u64>,
+    target: Option<u32>
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        command: Command::Run,
+        format: Format::Text,
+        days: String::new(),
+        answers: "answers.toml".to_string(),
+        source: Source::default(),
+        example: false,
+        top: 3,
+        percentiles: Vec::new(),
+        at_least: None,
+        target: None
     };
+    let mut food_options = false;
+    let mut positional: Vec<&str> = Vec::new();
+    let mut input: Option<Source> = None;
+    let mut dir = PathBuf::from(".");
+    let mut template = input::DEFAULT_TEMPLATE.to_string();
+    let mut args = args.iter();
 
-    match value {
-        1 => {
-            let day1 = read_file("in1.txt");
-            let part1 = food::find_most_food(&day1);
-            let part2 = food::find_top_three_holders(&day1);
-            println!("Question 1: {part1:?} {part2:?}");
-        },
-        2 => {
-            let day2 = read_file("in2.txt");
-            let part1 = rps::guide_score(&day2);
-            let part2 = rps::true_guide_score(&day2);
-            println!("Question 2: {part1}, {part2}");
-        },
-        3 => {
-            let day3 = read_file("in3.txt");
-            let part1 = reorg::value_shared_priorities(&day3);
-            let part2 = reorg::badge_groups(&day3);
-            println!("Question 3: {part1}, {part2}");
-        },
-        4 => {
-            let day4 = read_file("in4.txt");
-            let part1 = cleanup::how_many_containments(&day4);
-            let part2 = cleanup::how_many_overlaps(&day4);
-            println!("Question 4: {part1}, {part2}");
-        },
-        5 => {
-            let day5 = read_file("in5.txt");
-            let part1 = stacks::full_process(&day5);
-            let part2 = stacks::full_process_9001(&day5);
-            println!("Question 5: {part1}, {part2}");
-        },
-        6 => {
-            let day6 = read_file("in6.txt");
-            let part1 = tuning::first_marker(&day6, 4).unwrap();
-            let part2 = tuning::first_marker(&day6, 14).unwrap();
-            println!("Question 6: {part1}, {part2}");
-        },
-        7 => {
-            let day7 = read_file("in7.txt");
-            let part1 = filewalk::biggest_small_dirs(&day7);
-            let part2 = filewalk::smallest_big_dir(&day7);
-            println!("Question 7: {part1}, {part2}");
-        },
-        8 => {
-            let day8 = read_file("in8.txt");
-            let part1 = trees::count_visible_trees(&day8);
-            let part2 = trees::best_scenic_score(&day8);
-            println!("Question 8: {part1}, {part2}");
-        },
-        9 => {
-            let day9 = read_file("in9.txt");
-            let part1 = tail_follow::tail_visited_positions(&day9);
-            let part2 = tail_follow::many_tail_visited_positions(&day9);
-            println!("Question 9: {part1}, {part2}");
-        },
-        10 => {
-            let day10 = read_file("in10.txt");
-            let part1 = crt::sample_at_points(&day10);
-            println!("Question 10: {part1}");
-            crt::get_whole_image(&day10);
-        },
-        11 => {
-            let day11 = read_file("in11.txt");
-            let part1 = monkeys::monkey_business(&day11, false);
-            let part2 = monkeys::monkey_business(&day11, true);
-            println!("Question 11: {part1}, {part2}")
-        },
-        12 => {
-            let day12 = read_file("in12.txt");
-            let part1 = climbing::shortest_path(&day12);
-            let part2 = climbing::shortest_from_low(&day12);
-            println!("Question 12: {part1}, {part2}");
-        },
-        13 => {
-            let day13 = read_file("in13.txt");
-            let part1 = compare::evaluate_sorted(&day13);
-            let part2 = compare::distress_position(&day13);
-            println!("Question 13: {part1}, {part2}");
-        },
-        14 => {
-            let day14 = read_file("in14.txt");
-            let part1 = sand::maximum_static_sand(&day14);
-            let part2 = sand::maximum_floor_sand(&day14);
-            println!("Question 14: {part1}, {part2}");
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--example" => options.example = true,
+            "--answers" => options.answers = value()?.clone(),
+            "--input" => input = Some(Source::from_arg(value()?)),
+            "--inputs" => dir = PathBuf::from(value()?),
+            "--template" => template = value()?.clone(),
+            "--top" | "--percentile" | "--at-least" => {
+                let value = value()?;
+                let bad = || format!("{arg} needs a number, not {value}");
+                match arg.as_str() {
+                    "--top" => options.top = value.parse().map_err(|_| bad())?,
+                    "--percentile" => options.percentiles.push(value.parse().ok().filter(|p| (0.0..=100.0).contains(p)).ok_or_else(bad)?),
+                    _ => options.at_least = Some(value.parse().map_err(|_| bad())?)
+                }
+                food_options = true;
+            },
+            "--target" => {
+                let value = value()?;
+                options.target = Some(value.parse().map_err(|_| format!("--target needs a score, not {value}"))?);
+            },
+            "--format" => options.format = match value()?.as_str() {
+                "text" => Format::Text,
+                "json" => Format::Json,
+                f => return Err(format!("unknown format {f}, expected text or json"))
+            },
+            flag if flag.starts_with("--") => return Err(format!("unknown option {flag}")),
+            value => positional.push(value)
+        }
+    }
+
+    if positional.first() == Some(&"verify") {
+        options.command = Command::Verify;
+        positional.remove(0);
+        // Verifying checks everything unless told otherwise
+        options.days = "all".to_string();
+    } else if positional.first() == Some(&"food") {
+        options.command = Command::Food;
+        positional.remove(0);
+        // The report is only about day 1's elves
+        options.days = "1".to_string();
+    } else if positional.first() == Some(&"guide") {
+        options.command = Command::Guide;
+        positional.remove(0);
+        options.days = "2".to_string();
+    } else if positional.first() == Some(&"rucksacks") {
+        options.command = Command::Rucksacks;
+        positional.remove(0);
+        options.days = "3".to_string();
+    }
+    match positional[..] {
+        [] if options.command != Command::Run => {},
+        _ if options.command == Command::Food => return Err("food always reports on day 1".to_string()),
+        _ if options.command == Command::Guide => return Err("guide always decodes day 2".to_string()),
+        _ if options.command == Command::Rucksacks => return Err("rucksacks always checks day 3".to_string()),
+        [days] => options.days = days.to_string(),
+        _ => return Err(USAGE.to_string())
+    }
+    if options.command == Command::Verify && options.format == Format::Json {
+        return Err("verify only has text output".to_string());
+    }
+    if matches!(options.command, Command::Food | Command::Guide | Command::Rucksacks) && options.format == Format::Json {
+        return Err("food, guide and rucksacks only have text output".to_string());
+    }
+    if food_options && options.command != Command::Food {
+        return Err("--top, --percentile and --at-least only apply to food".to_string());
+    }
+    if options.target.is_some() && options.command != Command::Guide {
+        return Err("--target only applies to guide".to_string());
+    }
+    if options.percentiles.is_empty() {
+        options.percentiles = vec![25.0, 50.0, 75.0, 90.0];
+    }
+
+    options.source = match input {
+        Some(_) if options.example => return Err("--input can't be combined with --example".to_string()),
+        Some(_) if dir != Path::new(".") || template != input::DEFAULT_TEMPLATE => {
+            return Err("--input can't be combined with --inputs or --template".to_string());
         },
-        15 => {
-            let day15 = read_file("in15.txt");
-            let part1 = beacon::retro_part_one(&day15, 2000000);
-            let part2 = beacon::find_range_gap(&day15, 4000000);
-            println!("Question 15: {part1}, {part2}");
+        Some(source) => source,
+        None => Source::Directory { dir, template }
+    };
+    Ok(options)
+}
+
+fn run_selected<'a>(selected: Vec<&'a Day>, options: &Options) -> Vec<runner::Run<'a>> {
+    selected.into_iter().map(|day| match options.example {
+        true => runner::run_example(day),
+        false => runner::run(day, options.source.read(day.number))
+    }).collect()
+}
+
+fn verify(selected: Vec<&Day>, options: &Options) -> ExitCode {
+    let answers = if options.example {
+        Answers::from_examples(&selected)
+    } else {
+        let answers_file = &options.answers;
+        let answers = match input::read_file(Path::new(answers_file)) {
+            Ok(text) => answers::read_answers(&text),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return ExitCode::FAILURE;
+            }
+        };
+        match answers {
+            Ok(a) => a,
+            Err(e) => {
+                eprintln!("error: {answers_file}: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    };
+
+    let runs = run_selected(selected, options);
+    let (report, ok) = runner::verify(&runs, &answers);
+    println!("{report}");
+
+    if ok { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
+
+// Elves are numbered from one here, the way the puzzle text counts them
+fn food_report(options: &Options) -> Result<String, String> {
+    let input = match options.example {
+        true => food::EXAMPLE.to_string(),
+        false => options.source.read(1).map_err(|e| e.to_string())?
+    };
+    let report = food::food_report(&input).map_err(|e| e.to_string())?;
+    let elf = |(i, total): &(usize, u64)| format!("  Elf {:>4}: {total:>7} ({} items)", i + 1, report.counts[*i]);
+
+    let mut lines: Vec<String> = Vec::new();
+    let items: usize = report.counts.iter().sum();
+    lines.push(format!("{} elves carrying {} calories in {items} items", report.elves(), report.total()));
+    if let (Some(mean), Some(median)) = (report.mean(), report.median()) {
+        lines.push(format!("Mean {mean:.1}, median {median:.1}"));
+    }
+    let percentiles: Vec<String> = options.percentiles.iter()
+        .filter_map(|p| Some(format!("p{p} {}", report.percentile(*p)?)))
+        .collect();
+    lines.push(format!("Percentiles: {}", percentiles.join(", ")));
+
+    let top = report.top_with_ties(options.top);
+    let ties = match top.len() > options.top {
+        true => format!(", {} with ties", top.len()),
+        false => String::new()
+    };
+    lines.push(format!("Top {}{ties}:", options.top));
+    lines.extend(top.iter().map(elf));
+
+    if let Some(threshold) = options.at_least {
+        let carrying = report.at_least(threshold);
+        lines.push(format!("{} carrying at least {threshold}:", carrying.len()));
+        lines.extend(carrying.iter().map(elf));
+    }
+    Ok(lines.join("\n"))
+}
+
+// Scores the strategy guide under every way of reading its symbols
+fn guide_report(options: &Options) -> Result<String, String> {
+    let input = match options.example {
+        true => rps::EXAMPLE.to_string(),
+        false => options.source.read(2).map_err(|e| e.to_string())?
+    };
+    let guide = rps::read_guide(&input).map_err(|e| e.to_string())?;
+    let rules = rps::Rules::rock_paper_scissors();
+    let decodings = rps::decode_guide(&rules, &guide);
+    let line = |d: &rps::Decoding| format!("{:>8}  {}", d.score, d.reading.describe(&rules));
+
+    let mut lines: Vec<String> = Vec::new();
+    match options.target {
+        Some(target) => {
+            let matching = rps::matching_target(&decodings, target);
+            lines.push(format!("{} of {} readings score {target}", matching.len(), decodings.len()));
+            lines.extend(matching.into_iter().map(line));
         },
-        16 => {
-            let day16 = read_file("in16.txt");
-            let part1 = valves::part1(&day16);
-            let part2 = valves::part2(&day16);
-            println!("Question 16: {part1}, {part2}");
+        None => {
+            let best = decodings[0].score;
+            let tied = decodings.iter().filter(|d| d.score == best).count();
+            lines.push(format!("{} readings, best score {best} from {tied} of them", decodings.len()));
+            lines.extend(decodings.iter().map(line));
+        }
+    }
+    Ok(lines.join("\n"))
+}
+
+// Lists everything odd about the rucksacks, failing if there's anything to list
+fn check_rucksacks(options: &Options) -> ExitCode {
+    let input = match options.example {
+        true => Ok(reorg::EXAMPLE.to_string()),
+        false => options.source.read(3)
+    };
+    let input = match input {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let findings = reorg::validate(&input, reorg::COMPARTMENTS, reorg::GROUP_SIZE);
+    if findings.is_empty() {
+        println!("No problems found");
+        return ExitCode::SUCCESS;
+    }
+    for finding in &findings {
+        println!("{finding}");
+    }
+    ExitCode::FAILURE
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let days = puzzles::registry();
+
+    let options = match parse_args(&args) {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
         }
-        n => {
-            println!("No entry for day {n}");
+    };
+    let spec = &options.days;
+
+    let selected = match runner::select(&days, spec) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
         }
+    };
+
+    if options.source.is_single() && selected.len() > 1 {
+        eprintln!("--input can only be used with a single day");
+        return ExitCode::FAILURE;
+    }
+
+    if matches!(options.command, Command::Food | Command::Guide) {
+        let report = match options.command {
+            Command::Food => food_report(&options),
+            _ => guide_report(&options)
+        };
+        return match report {
+            Ok(report) => {
+                println!("{report}");
+                ExitCode::SUCCESS
+            },
+            Err(e) => {
+                eprintln!("error: {e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    if options.command == Command::Rucksacks {
+        return check_rucksacks(&options);
+    }
+
+    if options.command == Command::Verify {
+        return verify(selected, &options);
+    }
+
+    let single = spec.parse::<u8>().is_ok();
+    let runs = run_selected(selected, &options);
+
+    // A single day keeps the short output; anything else gets the table
+    if let (Format::Text, true, [run]) = (&options.format, single, &runs[..]) {
+        if let Err(e) = print_day(run) {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    match options.format {
+        Format::Text => println!("{}", runner::table(&runs)),
+        Format::Json => println!("{}", runner::json(&runs))
+    }
+
+    if runs.iter().any(|r| r.outcome.is_err()) {
+        return ExitCode::FAILURE;
     }
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,55 @@
+use std::str::FromStr;
+use crate::error::ParseError;
+
+// A line of puzzle input along with where it came from, for building errors
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SourceLine<'a> {
+    pub day: u8,
+    pub number: usize,
+    pub text: &'a str
+}
+
+impl<'a> SourceLine<'a> {
+    pub fn error(&self, token: &str, reason: impl Into<String>) -> ParseError {
+        ParseError::at(self.day, self.number, self.text, token, reason)
+    }
+
+    pub fn number<T: FromStr>(&self, token: &str) -> Result<T, ParseError> {
+        token.parse().map_err(|_| self.error(token, format!("expected a number, found `{token}`")))
+    }
+
+    pub fn word(&self, words: &[&'a str], index: usize, expected: &str) -> Result<&'a str, ParseError> {
+        words.get(index).copied().ok_or_else(|| {
+            let end = &self.text[self.text.len()..];
+            self.error(end, format!("expected {expected}"))
+        })
+    }
+}
+
+// Every non-blank line, numbered from the start of the input
+pub(crate) fn lines(day: u8, input: &str) -> impl Iterator<Item = SourceLine<'_>> {
+    input.lines().enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(move |(i, text)| SourceLine { day, number: i+1, text })
+}
+
+// Runs of non-blank lines separated by blank ones
+pub(crate) fn groups(day: u8, input: &str) -> Vec<Vec<SourceLine<'_>>> {
+    let mut output: Vec<Vec<SourceLine>> = Vec::new();
+    let mut current: Vec<SourceLine> = Vec::new();
+
+    for (i, text) in input.lines().enumerate() {
+        if text.trim().is_empty() {
+            if !current.is_empty() {
+                output.push(current);
+                current = Vec::new();
+            }
+        } else {
+            current.push(SourceLine { day, number: i+1, text });
+        }
+    }
+    if !current.is_empty() {
+        output.push(current);
+    }
+    output
+}
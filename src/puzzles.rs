@@ -14,3 +14,87 @@ pub mod compare;
 pub mod sand;
 pub mod beacon;
 pub mod valves;
+pub mod pyroclastic;
+pub mod boulders;
+pub mod minerals;
+pub mod mixing;
+pub mod monkey_math;
+pub mod monkey_map;
+pub mod diffusion;
+pub mod blizzard;
+pub mod snafu;
+
+use crate::solver::{Answer, Puzzle};
+
+// The sample from a day's puzzle text, the answers it should give, and a solver set up for it
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Answer,
+    pub part2: Answer,
+    pub solver: Box<dyn Puzzle>
+}
+
+impl Example {
+    pub fn new(input: &'static str, solver: impl Puzzle + 'static, part1: impl Into<Answer>, part2: impl Into<Answer>) -> Self {
+        Example { input, part1: part1.into(), part2: part2.into(), solver: Box::new(solver) }
+    }
+}
+
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    pub solver: Box<dyn Puzzle>,
+    pub example: Example
+}
+
+impl Day {
+    fn new(number: u8, title: &'static str, solver: impl Puzzle + 'static, example: Example) -> Self {
+        Day { number, title, solver: Box::new(solver), example }
+    }
+}
+
+// Every solved day, in order. Adding a day means adding its module above and an entry here.
+pub fn registry() -> Vec<Day> {
+    vec![
+        Day::new(1, "Calorie Counting", food::CalorieCounting, food::example()),
+        Day::new(2, "Rock Paper Scissors", rps::RockPaperScissors, rps::example()),
+        Day::new(3, "Rucksack Reorganization", reorg::RucksackReorganization, reorg::example()),
+        Day::new(4, "Camp Cleanup", cleanup::CampCleanup, cleanup::example()),
+        Day::new(5, "Supply Stacks", stacks::SupplyStacks, stacks::example()),
+        Day::new(6, "Tuning Trouble", tuning::TuningTrouble, tuning::example()),
+        Day::new(7, "No Space Left On Device", filewalk::NoSpaceLeft, filewalk::example()),
+        Day::new(8, "Treetop Tree House", trees::TreetopTreeHouse, trees::example()),
+        Day::new(9, "Rope Bridge", tail_follow::RopeBridge, tail_follow::example()),
+        Day::new(10, "Cathode-Ray Tube", crt::CathodeRayTube, crt::example()),
+        Day::new(11, "Monkey in the Middle", monkeys::MonkeyInTheMiddle, monkeys::example()),
+        Day::new(12, "Hill Climbing Algorithm", climbing::HillClimbing, climbing::example()),
+        Day::new(13, "Distress Signal", compare::DistressSignal, compare::example()),
+        Day::new(14, "Regolith Reservoir", sand::RegolithReservoir, sand::example()),
+        Day::new(15, "Beacon Exclusion Zone", beacon::BeaconExclusionZone { row: 2000000, bound: 4000000 }, beacon::example()),
+        Day::new(16, "Proboscidea Volcanium", valves::ProboscideaVolcanium, valves::example()),
+        Day::new(17, "Pyroclastic Flow", pyroclastic::PyroclasticFlow, pyroclastic::example()),
+        Day::new(18, "Boiling Boulders", boulders::BoilingBoulders, boulders::example()),
+        Day::new(19, "Not Enough Minerals", minerals::NotEnoughMinerals, minerals::example()),
+        Day::new(20, "Grove Positioning System", mixing::GrovePositioningSystem, mixing::example()),
+        Day::new(21, "Monkey Math", monkey_math::MonkeyMath, monkey_math::example()),
+        Day::new(22, "Monkey Map", monkey_map::MonkeyMap, monkey_map::example()),
+        Day::new(23, "Unstable Diffusion", diffusion::UnstableDiffusion, diffusion::example()),
+        Day::new(24, "Blizzard Basin", blizzard::BlizzardBasin, blizzard::example()),
+        Day::new(25, "Full of Hot Air", snafu::FullOfHotAir, snafu::example()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for day in registry() {
+            let example = &day.example;
+            let solution = example.solver.solve(example.input).unwrap();
+            assert_eq!(solution.part1, example.part1, "day {} part 1", day.number);
+            assert_eq!(solution.part2, example.part2, "day {} part 2", day.number);
+        }
+    }
+}
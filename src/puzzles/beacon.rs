@@ -1,19 +1,52 @@
 use std::cmp::max;
+use crate::error::ParseError;
+use crate::parsing::{lines, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 15;
+
+pub const EXAMPLE: &str = r"Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+pub type Coord = (i32, i32);
+pub type SensorBeacon = (Coord, Coord);
+
+// Reads words like `x=2,` or `y=-18:`
+fn quick_parse(line: &SourceLine, words: &[&str], index: usize) -> Result<i32, ParseError> {
+    let word = line.word(words, index, "a coordinate")?;
+    let value = word.get(2..).unwrap_or("").trim_end_matches([',', ':']);
+    if !(word.starts_with("x=") || word.starts_with("y=")) {
+        return Err(line.error(word, format!("expected a coordinate like `x=2`, found `{word}`")));
+    }
+    line.number(value)
+}
 
-type Coord = (i32, i32);
-type SensorBeacon = (Coord, Coord);
-
-fn read_report(input: &String) -> Vec<SensorBeacon> {
-    let quick_parse = |s: &str| -> i32 {s.chars().filter(|p| p.is_digit(10) || p == &'-').collect::<String>().parse().unwrap()};
-
-    input.trim().lines().map(|line| {
-        let words: Vec<&str> = line.trim().split(" ").collect();
-        let x1 = quick_parse(words[2]);
-        let y1 = quick_parse(words[3]);
-        let x2 = quick_parse(words[8]);
-        let y2 = quick_parse(words[9]);
-        ((x1, y1), (x2, y2))
-    }).collect()
+pub fn read_report(input: &str) -> Result<Vec<SensorBeacon>, ParseError> {
+    let report = lines(DAY, input).map(|line| {
+        let words: Vec<&str> = line.text.split_whitespace().collect();
+        let x1 = quick_parse(&line, &words, 2)?;
+        let y1 = quick_parse(&line, &words, 3)?;
+        let x2 = quick_parse(&line, &words, 8)?;
+        let y2 = quick_parse(&line, &words, 9)?;
+        Ok(((x1, y1), (x2, y2)))
+    }).collect::<Result<Vec<_>, _>>()?;
+    if report.is_empty() {
+        return Err(ParseError::input(DAY, "there are no sensors"));
+    }
+    Ok(report)
 }
 
 type View = (i32, i32);
@@ -28,15 +61,17 @@ fn range_for_row(sr: SensorRadius, row: i32) -> Option<View> {
     }
 
     let thickness = relative_row.abs().abs_diff(r) as i32;
-    return Some((x-thickness, x+thickness));
+    Some((x-thickness, x+thickness))
 }
 
-fn resolve_ranges_for_row(srs: &Vec<SensorRadius>, row: i32) -> Vec<View> {
-    let mut views: Vec<View> = srs.iter().map(|sr| range_for_row(*sr, row)).filter(|r| r.is_some())
-        .map(|r| r.unwrap()).collect();
+fn resolve_ranges_for_row(srs: &[SensorRadius], row: i32) -> Vec<View> {
+    let mut views: Vec<View> = srs.iter().filter_map(|sr| range_for_row(*sr, row)).collect();
     views.sort();
 
     let mut merged: Vec<View> = Vec::new();
+    if views.is_empty() {
+        return merged;
+    }
 
     let (mut start, mut end) = views[0];
 
@@ -54,39 +89,70 @@ fn resolve_ranges_for_row(srs: &Vec<SensorRadius>, row: i32) -> Vec<View> {
     merged
 }
 
-pub fn retro_part_one(input: &String, target_row: i32) -> i32 {
-    let sbs = read_report(input);
-    let sensor_radii = calculate_radii(sbs);
-
-    let rs = resolve_ranges_for_row(&sensor_radii, target_row);
+fn covered_in_row(sensor_radii: &[SensorRadius], target_row: i32) -> i32 {
+    let rs = resolve_ranges_for_row(sensor_radii, target_row);
     rs.iter().map(|(start, end)| end - start).sum()
 }
 
-pub fn find_range_gap(input: &String, square_max: i32) -> i64 {
-    let sbs = read_report(input);
-    let sensor_radii = calculate_radii(sbs);
-
+fn tuning_frequency(sensor_radii: &[SensorRadius], square_max: i32) -> Option<i64> {
     for i in 0..=square_max {
-        let k = resolve_ranges_for_row(&sensor_radii, i);
+        let k = resolve_ranges_for_row(sensor_radii, i);
         if k.len() == 2 {
             let row = i;
             let (_x, y) = k[0];
             let col = y + 1;
-            return (col as i64) * 4000000 + row as i64;
+            return Some((col as i64) * 4000000 + row as i64);
         }
     }
-    panic!("Nope!");
+    None
+}
+
+pub fn retro_part_one(input: &str, target_row: i32) -> Result<i32, ParseError> {
+    let sbs = read_report(input)?;
+    let sensor_radii = calculate_radii(sbs);
+    Ok(covered_in_row(&sensor_radii, target_row))
 }
 
-type SensorRadius = (Coord, i32);
+pub fn find_range_gap(input: &str, square_max: i32) -> Result<Option<i64>, ParseError> {
+    let sbs = read_report(input)?;
+    let sensor_radii = calculate_radii(sbs);
+    Ok(tuning_frequency(&sensor_radii, square_max))
+}
 
-fn calculate_radii(sensors: Vec<SensorBeacon>) -> Vec<SensorRadius>{
+pub type SensorRadius = (Coord, i32);
+
+pub fn calculate_radii(sensors: Vec<SensorBeacon>) -> Vec<SensorRadius>{
     sensors.iter().map(|((x, y), (a, b))| {
         let radius: i32 = (x.abs_diff(*a) + y.abs_diff(*b)) as i32;
-        return ((*x, *y), radius);
+        ((*x, *y), radius)
     }).collect()
 }
 
+// The row to count and the search bound differ between the example and the real input
+pub struct BeaconExclusionZone {
+    pub row: i32,
+    pub bound: i32
+}
+
+impl Solver for BeaconExclusionZone {
+    type Parsed = Vec<SensorRadius>;
+
+    fn parse(&self, input: &str) -> Result<Vec<SensorRadius>, ParseError> {
+        Ok(calculate_radii(read_report(input)?))
+    }
+
+    fn part1(&self, sensor_radii: &Vec<SensorRadius>) -> Answer {
+        covered_in_row(sensor_radii, self.row).into()
+    }
+
+    fn part2(&self, sensor_radii: &Vec<SensorRadius>) -> Answer {
+        tuning_frequency(sensor_radii, self.bound).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, BeaconExclusionZone { row: 10, bound: 20 }, 26, 56000011i64)
+}
 
 #[cfg(test)]
 mod tests {
@@ -94,24 +160,13 @@ mod tests {
 
     #[test]
     fn example_one() {
-        let input = r"
-        Sensor at x=2, y=18: closest beacon is at x=-2, y=15
-        Sensor at x=9, y=16: closest beacon is at x=10, y=16
-        Sensor at x=13, y=2: closest beacon is at x=15, y=3
-        Sensor at x=12, y=14: closest beacon is at x=10, y=16
-        Sensor at x=10, y=20: closest beacon is at x=10, y=16
-        Sensor at x=14, y=17: closest beacon is at x=10, y=16
-        Sensor at x=8, y=7: closest beacon is at x=2, y=10
-        Sensor at x=2, y=0: closest beacon is at x=2, y=10
-        Sensor at x=0, y=11: closest beacon is at x=2, y=10
-        Sensor at x=20, y=14: closest beacon is at x=25, y=17
-        Sensor at x=17, y=20: closest beacon is at x=21, y=22
-        Sensor at x=16, y=7: closest beacon is at x=15, y=3
-        Sensor at x=14, y=3: closest beacon is at x=15, y=3
-        Sensor at x=20, y=1: closest beacon is at x=15, y=3
-        ".to_string();
-
-        assert_eq!(retro_part_one(&input, 10), 26);
-        assert_eq!(find_range_gap(&input, 20), 56000011);
+        assert_eq!(retro_part_one(EXAMPLE, 10), Ok(26));
+        assert_eq!(find_range_gap(EXAMPLE, 20), Ok(Some(56000011)));
+    }
+
+    #[test]
+    fn bad_coordinate() {
+        let error = read_report("Sensor at x=2, y=1q: closest beacon is at x=-2, y=15").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 18));
     }
 }
@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+use crate::error::ParseError;
+use crate::parsing::lines;
+use crate::puzzles::Example;
+use crate::puzzles::climbing::{get_surrounds, Coord};
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 24;
+
+pub const EXAMPLE: &str = r"#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#";
+
+pub type Grid = Vec<Vec<u8>>;
+//   Valley = (Map, Start, End);
+pub type Valley = (Grid, Coord, Coord);
+
+pub fn read_valley(input: &str) -> Result<Valley, ParseError> {
+    let mut grid: Grid = Vec::new();
+    for line in lines(DAY, input) {
+        let text = line.text.trim();
+        if let Some(i) = text.find(|c| !"#.<>^v".contains(c)) {
+            return Err(line.error(&text[i..], "expected a wall, ground or a blizzard"));
+        }
+        if let Some(first) = grid.first() {
+            if first.len() != text.len() {
+                return Err(line.error(text, format!("expected {} squares in the row, found {}", first.len(), text.len())));
+            }
+        }
+        // Only the top and bottom walls have a way through
+        for side in [&text[..text.len().min(1)], &text[text.len().saturating_sub(1)..]] {
+            if side != "#" {
+                return Err(line.error(side, "expected the valley's side wall"));
+            }
+        }
+        grid.push(text.as_bytes().to_vec());
+    }
+    if grid.len() < 3 || grid[0].len() < 3 {
+        return Err(ParseError::input(DAY, "the valley is too small to have any room inside its walls"));
+    }
+
+    let gap = |row: usize| grid[row].iter().position(|t| *t == b'.').map(|column| (row, column));
+    let start = gap(0).ok_or_else(|| ParseError::input(DAY, "there is no way in through the top wall"))?;
+    let end = gap(grid.len() - 1).ok_or_else(|| ParseError::input(DAY, "there is no way out through the bottom wall"))?;
+    Ok((grid, start, end))
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+// Blizzards wrap round inside the walls, so rather than moving them all each minute,
+// look back along the row and column for any that would have reached this square by now
+fn blizzard_at(grid: &Grid, (row, column): Coord, time: usize) -> bool {
+    let height = grid.len() - 2;
+    let width = grid[0].len() - 2;
+    if row == 0 || row > height {
+        return false;
+    }
+    let (r, c) = (row - 1, column - 1);
+    let (dr, dc) = (time % height, time % width);
+
+    grid[row][1 + (c + width - dc) % width] == b'>'
+        || grid[row][1 + (c + dc) % width] == b'<'
+        || grid[1 + (r + height - dr) % height][column] == b'v'
+        || grid[1 + (r + dr) % height][column] == b'^'
+}
+
+// Breadth first over (square, minute), where minutes repeat once every blizzard is back
+// where it began. Returns the minute we arrive.
+fn crossing((grid, _, _): &Valley, from: Coord, to: Coord, start_time: usize) -> Option<usize> {
+    let height = grid.len();
+    let width = grid[0].len();
+    let period = (height - 2) * (width - 2) / gcd(height - 2, width - 2);
+
+    let mut seen: HashSet<(Coord, usize)> = HashSet::new();
+    let mut frontier: Vec<Coord> = vec![from];
+    let mut time = start_time;
+
+    while !frontier.is_empty() {
+        time += 1;
+        let mut next: Vec<Coord> = Vec::new();
+        for pos in frontier {
+            let mut moves = get_surrounds(pos, width, height);
+            moves.push(pos);
+            for square in moves {
+                if square == to {
+                    return Some(time);
+                }
+                if grid[square.0][square.1] == b'#' || blizzard_at(grid, square, time) {
+                    continue;
+                }
+                if seen.insert((square, time % period)) {
+                    next.push(square);
+                }
+            }
+        }
+        frontier = next;
+    }
+    None
+}
+
+fn fastest_crossing(valley: &Valley) -> Option<usize> {
+    let (_, start, end) = valley;
+    crossing(valley, *start, *end, 0)
+}
+
+// There, back for the snacks, and there again
+fn snack_trip(valley: &Valley) -> Option<usize> {
+    let (_, start, end) = valley;
+    let there = crossing(valley, *start, *end, 0)?;
+    let back = crossing(valley, *end, *start, there)?;
+    crossing(valley, *start, *end, back)
+}
+
+pub fn shortest_crossing(input: &str) -> Result<Option<usize>, ParseError> {
+    Ok(fastest_crossing(&read_valley(input)?))
+}
+
+pub fn shortest_snack_trip(input: &str) -> Result<Option<usize>, ParseError> {
+    Ok(snack_trip(&read_valley(input)?))
+}
+
+pub struct BlizzardBasin;
+
+impl Solver for BlizzardBasin {
+    type Parsed = Valley;
+
+    fn parse(&self, input: &str) -> Result<Valley, ParseError> {
+        read_valley(input)
+    }
+
+    fn part1(&self, valley: &Valley) -> Answer {
+        fastest_crossing(valley).into()
+    }
+
+    fn part2(&self, valley: &Valley) -> Answer {
+        snack_trip(valley).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, BlizzardBasin, 18, 54)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moving_blizzards() {
+        let (grid, _, _) = read_valley(EXAMPLE).unwrap();
+        // The `>` starting at row 1, column 1 is two squares right after two minutes
+        assert!(blizzard_at(&grid, (1, 1), 0));
+        assert!(blizzard_at(&grid, (1, 3), 2));
+        // A full cycle puts everything back
+        let period = 4 * 6 / gcd(4, 6);
+        for row in 1..5 {
+            for column in 1..7 {
+                assert_eq!(blizzard_at(&grid, (row, column), 0), blizzard_at(&grid, (row, column), period));
+            }
+        }
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(shortest_crossing(EXAMPLE), Ok(Some(18)));
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(shortest_snack_trip(EXAMPLE), Ok(Some(54)));
+    }
+
+    #[test]
+    fn no_way_out() {
+        let error = read_valley("#.##\n#..#\n####").unwrap_err();
+        assert_eq!(error.reason, "there is no way out through the bottom wall");
+    }
+
+    #[test]
+    fn gap_in_side_wall() {
+        let error = read_valley("#.###\n.>..#\n#...#\n###.#").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
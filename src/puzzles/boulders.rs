@@ -0,0 +1,136 @@
+use std::collections::{HashSet, VecDeque};
+use crate::error::ParseError;
+use crate::parsing::lines;
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 18;
+
+pub const EXAMPLE: &str = r"2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+pub type Cube = (i32, i32, i32);
+pub type Droplet = HashSet<Cube>;
+
+const SIDES: [Cube; 6] = [(1, 0, 0), (-1, 0, 0), (0, 1, 0), (0, -1, 0), (0, 0, 1), (0, 0, -1)];
+
+pub fn read_droplet(input: &str) -> Result<Droplet, ParseError> {
+    let mut droplet: Droplet = HashSet::new();
+    for line in lines(DAY, input) {
+        let text = line.text.trim();
+        let parts: Vec<&str> = text.split(',').collect();
+        let [x, y, z] = parts[..] else {
+            return Err(line.error(text, "expected a cube like `2,2,2`"));
+        };
+        droplet.insert((line.number(x)?, line.number(y)?, line.number(z)?));
+    }
+    if droplet.is_empty() {
+        return Err(ParseError::input(DAY, "there are no cubes"));
+    }
+    Ok(droplet)
+}
+
+fn neighbours((x, y, z): Cube) -> impl Iterator<Item = Cube> {
+    SIDES.iter().map(move |(dx, dy, dz)| (x + dx, y + dy, z + dz))
+}
+
+fn surface_area(droplet: &Droplet) -> usize {
+    droplet.iter()
+        .map(|c| neighbours(*c).filter(|n| !droplet.contains(n)).count())
+        .sum()
+}
+
+// Flood fills the air around the droplet, one cube bigger than it on every side,
+// counting each time the water touches a face
+fn exterior_area(droplet: &Droplet) -> usize {
+    let low = |f: fn(&Cube) -> i32| droplet.iter().map(f).min().unwrap() - 1;
+    let high = |f: fn(&Cube) -> i32| droplet.iter().map(f).max().unwrap() + 1;
+    let (min_x, min_y, min_z) = (low(|c| c.0), low(|c| c.1), low(|c| c.2));
+    let (max_x, max_y, max_z) = (high(|c| c.0), high(|c| c.1), high(|c| c.2));
+    let inside = |(x, y, z): &Cube| {
+        (min_x..=max_x).contains(x) && (min_y..=max_y).contains(y) && (min_z..=max_z).contains(z)
+    };
+
+    let start = (min_x, min_y, min_z);
+    let mut seen: HashSet<Cube> = HashSet::from([start]);
+    let mut queue: VecDeque<Cube> = VecDeque::from([start]);
+    let mut faces = 0;
+
+    while let Some(cube) = queue.pop_front() {
+        for next in neighbours(cube).filter(inside) {
+            if droplet.contains(&next) {
+                faces += 1;
+            } else if seen.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    faces
+}
+
+pub fn total_surface(input: &str) -> Result<usize, ParseError> {
+    Ok(surface_area(&read_droplet(input)?))
+}
+
+pub fn outside_surface(input: &str) -> Result<usize, ParseError> {
+    Ok(exterior_area(&read_droplet(input)?))
+}
+
+pub struct BoilingBoulders;
+
+impl Solver for BoilingBoulders {
+    type Parsed = Droplet;
+
+    fn parse(&self, input: &str) -> Result<Droplet, ParseError> {
+        read_droplet(input)
+    }
+
+    fn part1(&self, droplet: &Droplet) -> Answer {
+        surface_area(droplet).into()
+    }
+
+    fn part2(&self, droplet: &Droplet) -> Answer {
+        exterior_area(droplet).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, BoilingBoulders, 64, 58)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_cubes() {
+        assert_eq!(total_surface("1,1,1\n2,1,1"), Ok(10));
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(total_surface(EXAMPLE), Ok(64));
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(outside_surface(EXAMPLE), Ok(58));
+    }
+
+    #[test]
+    fn missing_coordinate() {
+        let error = read_droplet("2,2,2\n1,2").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
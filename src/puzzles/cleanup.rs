@@ -1,19 +1,31 @@
+use crate::error::ParseError;
+use crate::parsing::{lines, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
 
-type Range = (u32, u32);
-type RangePair = (Range, Range);
+const DAY: u8 = 4;
 
-fn read_range(input: &str) -> Range {
-    let numbers: Vec<u32> = input.split("-").map(|p| p.parse().expect("Must be a number")).collect();
-    let one = numbers.get(0).expect("Must have a first number");
-    let two = numbers.get(1).expect("Must have a second number");
-    (*one, *two)
+pub const EXAMPLE: &str = r"2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+pub type Range = (u32, u32);
+pub type RangePair = (Range, Range);
+
+fn read_range(line: &SourceLine, input: &str) -> Result<Range, ParseError> {
+    let (one, two) = input.split_once('-')
+        .ok_or_else(|| line.error(input, "expected a range like `2-4`"))?;
+    Ok((line.number(one)?, line.number(two)?))
 }
 
-fn read_pairs(input: &str) -> RangePair {
-    let parts: Vec<Range> = input.split(",").map(|s| read_range(s)).collect();
-    let one = parts.get(0).expect("Must have a first range");
-    let two = parts.get(1).expect("Must have a second range");
-    (*one, *two)
+fn read_pairs(line: &SourceLine) -> Result<RangePair, ParseError> {
+    let text = line.text.trim();
+    let (one, two) = text.split_once(',')
+        .ok_or_else(|| line.error(text, "expected two ranges separated by a comma"))?;
+    Ok((read_range(line, one)?, read_range(line, two)?))
 }
 
 fn has_containment(pair: RangePair) -> bool{
@@ -38,52 +50,73 @@ fn has_overlap(pair: RangePair) -> bool {
     false
 }
 
-pub fn how_many_containments(input: &String) -> u32 {
-    input.trim().lines()
-        .map(|l| read_pairs(l))
-        .filter(|rp| has_containment(*rp))
+pub fn read_assignments(input: &str) -> Result<Vec<RangePair>, ParseError> {
+    lines(DAY, input).map(|line| read_pairs(&line)).collect()
+}
+
+fn count_containments(pairs: &[RangePair]) -> u32 {
+    pairs.iter()
+        .filter(|rp| has_containment(**rp))
         .count() as u32
 }
 
-pub fn how_many_overlaps(input: &String) -> u32 {
-    input.trim().lines()
-        .map(|l| read_pairs(l))
-        .filter(|rp| has_overlap(*rp))
+fn count_overlaps(pairs: &[RangePair]) -> u32 {
+    pairs.iter()
+        .filter(|rp| has_overlap(**rp))
         .count() as u32
 }
 
+pub fn how_many_containments(input: &str) -> Result<u32, ParseError> {
+    Ok(count_containments(&read_assignments(input)?))
+}
+
+pub fn how_many_overlaps(input: &str) -> Result<u32, ParseError> {
+    Ok(count_overlaps(&read_assignments(input)?))
+}
+
+pub struct CampCleanup;
+
+impl Solver for CampCleanup {
+    type Parsed = Vec<RangePair>;
+
+    fn parse(&self, input: &str) -> Result<Vec<RangePair>, ParseError> {
+        read_assignments(input)
+    }
+
+    fn part1(&self, pairs: &Vec<RangePair>) -> Answer {
+        count_containments(pairs).into()
+    }
+
+    fn part2(&self, pairs: &Vec<RangePair>) -> Answer {
+        count_overlaps(pairs).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, CampCleanup, 2, 4)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn part_one() {
-        let input = r"
-2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8
-        ".to_string();
-
-        let count = how_many_containments(&input);
+        let count = how_many_containments(EXAMPLE).unwrap();
 
         assert_eq!(count, 2);
     }
 
     #[test]
     fn part_two() {
-        let input = r"
-2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8
-        ".to_string();
-
-        let count = how_many_overlaps(&input);
+        let count = how_many_overlaps(EXAMPLE).unwrap();
         assert_eq!(count, 4);
     }
+
+    #[test]
+    fn missing_range_end() {
+        let error = read_assignments("2-4,6-8\n2-3,4").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.reason, "expected a range like `2-4`");
+    }
 }
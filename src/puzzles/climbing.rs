@@ -1,34 +1,65 @@
 use std::collections::{HashSet, VecDeque};
 use std::cmp::min;
+use crate::error::ParseError;
+use crate::parsing::lines;
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
 
-type HeightGrid = Vec<Vec<u8>>;
-type Coord = (usize, usize);
+const DAY: u8 = 12;
+
+pub const EXAMPLE: &str = r"Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+pub type HeightGrid = Vec<Vec<u8>>;
+pub type Coord = (usize, usize);
 //   World = (Map, Start, End);
-type World = (HeightGrid, Coord, Coord);
-
-fn parse_elevations(input: &String) -> World {
-    let mut start_point: Coord = (0, 0);
-    let mut end_point: Coord = (0, 0);
-    let grid = input.trim().lines().enumerate().map(|(row, l)| l.bytes()
-        .enumerate().map(|(column, b)|{
-            if b == b'S' {
-                start_point = (row, column);
-                return 0;
+pub type World = (HeightGrid, Coord, Coord);
+
+pub fn parse_elevations(input: &str) -> Result<World, ParseError> {
+    let mut start_point: Option<Coord> = None;
+    let mut end_point: Option<Coord> = None;
+    let mut grid: HeightGrid = Vec::new();
+
+    for (row, line) in lines(DAY, input).enumerate() {
+        let text = line.text.trim();
+        let heights = text.char_indices().map(|(column, c)| {
+            match c {
+                'S' if start_point.is_some() => Err(line.error(&text[column..], "there is more than one start")),
+                'E' if end_point.is_some() => Err(line.error(&text[column..], "there is more than one end")),
+                'S' => {
+                    start_point = Some((row, column));
+                    Ok(0)
+                },
+                'E' => {
+                    end_point = Some((row, column));
+                    Ok(b'z'-b'a')
+                },
+                'a'..='z' => Ok(c as u8 - b'a'),
+                _ => Err(line.error(&text[column..], format!("`{c}` is not an elevation")))
             }
-            if b == b'E' {
-                end_point = (row, column);
-                return b'z'-b'a';
+        }).collect::<Result<Vec<u8>, ParseError>>()?;
+
+        if let Some(first) = grid.first() {
+            if first.len() != heights.len() {
+                return Err(line.error(text, format!("expected {} squares in the row, found {}", first.len(), heights.len())));
             }
-            b - b'a'
-        }).collect()).collect();
-    (grid, start_point, end_point)
+        }
+        grid.push(heights);
+    }
+
+    let start_point = start_point.ok_or_else(|| ParseError::input(DAY, "the map has no start marked `S`"))?;
+    let end_point = end_point.ok_or_else(|| ParseError::input(DAY, "the map has no end marked `E`"))?;
+    Ok((grid, start_point, end_point))
 }
 
 fn taxi_distance((a, b): Coord, (x, y): Coord) -> usize {
     a.abs_diff(x) + b.abs_diff(y)
 }
 
-fn get_surrounds((row, column): Coord, width: usize, height: usize) -> Vec<Coord> {
+pub(crate) fn get_surrounds((row, column): Coord, width: usize, height: usize) -> Vec<Coord> {
     let mut output: Vec<Coord> = Vec::new();
     if let Some(v) = row.checked_sub(1) {
         output.push((v, column));
@@ -47,7 +78,7 @@ fn get_surrounds((row, column): Coord, width: usize, height: usize) -> Vec<Coord
 
 // (Current, Visited, Current Cost, Estimated Cost)
 type SearchNode = (Coord, u32, usize);
-fn heuristic_search(w: World, starting_nodes: Vec<SearchNode>) -> u32{
+fn heuristic_search(w: World, starting_nodes: Vec<SearchNode>) -> Option<u32>{
     let (grid, start, end) = w;
     let height = grid.len();
     let width = grid[0].len();
@@ -59,7 +90,7 @@ fn heuristic_search(w: World, starting_nodes: Vec<SearchNode>) -> u32{
 
     let mut best_steps: u32 = u32::MAX;
 
-    while nodes.len() > 0 {
+    while !nodes.is_empty() {
         let (pos, steps, _) = nodes.pop_front().unwrap();
         if visited.contains(&pos) {
             continue;
@@ -91,16 +122,15 @@ fn heuristic_search(w: World, starting_nodes: Vec<SearchNode>) -> u32{
 
     }
     
-    best_steps
+    // The end may be walled off by cliffs
+    (best_steps != u32::MAX).then_some(best_steps)
 }
 
-pub fn shortest_path(input: &String) -> u32 {
-    let world = parse_elevations(&input);
+fn search_from_start(world: World) -> Option<u32> {
     heuristic_search(world, vec![])
 }
 
-pub fn shortest_from_low(input: &String) -> u32 {
-    let world = parse_elevations(&input);
+fn search_from_low(world: World) -> Option<u32> {
     let mut lowest_points: Vec<SearchNode> = Vec::new();
     world.0.iter().enumerate().for_each(|(row, line)| {
         line.iter().enumerate().for_each(|(column, v)|{
@@ -112,20 +142,54 @@ pub fn shortest_from_low(input: &String) -> u32 {
     heuristic_search(world, lowest_points)
 }
 
+pub fn shortest_path(input: &str) -> Result<Option<u32>, ParseError> {
+    Ok(search_from_start(parse_elevations(input)?))
+}
+
+pub fn shortest_from_low(input: &str) -> Result<Option<u32>, ParseError> {
+    Ok(search_from_low(parse_elevations(input)?))
+}
+
+pub struct HillClimbing;
+
+impl Solver for HillClimbing {
+    type Parsed = World;
+
+    fn parse(&self, input: &str) -> Result<World, ParseError> {
+        parse_elevations(input)
+    }
+
+    fn part1(&self, world: &World) -> Answer {
+        search_from_start(world.clone()).into()
+    }
+
+    fn part2(&self, world: &World) -> Answer {
+        search_from_low(world.clone()).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, HillClimbing, 31, 29)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn example_one() {
-        let input = r"
-Sabqponm
-abcryxxl
-accszExk
-acctuvwj
-abdefghi".to_string();
+        assert_eq!(shortest_path(EXAMPLE), Ok(Some(31)));
+        assert_eq!(shortest_from_low(EXAMPLE), Ok(Some(29)));
+    }
 
-        assert_eq!(shortest_path(&input), 31);
-        assert_eq!(shortest_from_low(&input), 29);
+    #[test]
+    fn unreachable_end() {
+        assert_eq!(shortest_path("SbE"), Ok(None));
+    }
+
+    #[test]
+    fn missing_end() {
+        let error = parse_elevations("Sabq\nabcr").unwrap_err();
+        assert_eq!(error.reason, "the map has no end marked `E`");
     }
 }
\ No newline at end of file
@@ -1,121 +1,162 @@
-use std::str::Chars;
 use std::cmp::{max, Ordering};
+use crate::error::ParseError;
+use crate::parsing::{groups, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
 
-fn read_pairs(input: &String) -> Vec<(RecurPacket, RecurPacket)>{
-    let vectored: Vec<&str> = input.trim().lines().collect();
-    let grouped: Vec<&[&str]> = vectored.split(|s| *s == "").collect();
+const DAY: u8 = 13;
 
-    grouped.iter().map(|group| {
-        let first = group[0];
-        let second = group[1];
-        (recur_parse_root(first), recur_parse_root(second))
+pub const EXAMPLE: &str = r"[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+pub type PacketPair = (RecurPacket, RecurPacket);
+
+pub fn read_pairs(input: &str) -> Result<Vec<PacketPair>, ParseError> {
+    groups(DAY, input).iter().map(|group| {
+        match group[..] {
+            [first, second] => Ok((parse_packet(&first)?, parse_packet(&second)?)),
+            [_] => Err(ParseError::input(DAY, format!("packet on line {} has no partner", group[0].number))),
+            _ => Err(group[2].error(group[2].text.trim(), "expected a blank line between pairs"))
+        }
     }).collect()
 }
 
-fn read_all_together(input: &String) -> Vec<RecurPacket>{
-    input.trim().lines().filter(|l| *l != "").map(|l| recur_parse_root(l)).collect()
-}
 
-#[derive(Debug, Clone, PartialEq)]
-enum RecurPacket {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecurPacket {
     Value(u32),
     Arr (Vec<RecurPacket>)
 }
 
-fn recur_parse_root(input: &str) -> RecurPacket {
-    let mut iter = input.chars();
-    match recur_parse(&mut iter) {
-        RecurPacket::Arr(ar) => {
-            ar[0].to_owned()
-        },
-        n => n
-    }
+pub fn recur_parse_root(input: &str) -> Result<RecurPacket, ParseError> {
+    parse_packet(&SourceLine { day: DAY, number: 1, text: input })
 }
 
-fn recur_parse(current_text: &mut Chars) -> RecurPacket{
-    use RecurPacket::*;
-
-    let mut value_arr: Vec<RecurPacket> = Vec::new();
-
-    while let Some(c) = current_text.next() {
-        if c.is_digit(10) {
-            let mut number = vec![c];
-            current_text.by_ref().take_while(|n| n.is_digit(10))
-                .for_each(|n| number.push(n));
-            value_arr.push(Value (number.iter().collect::<String>().parse().unwrap()));
-            continue;
-        }
-
-        match c {
-            '[' => value_arr.push(recur_parse(current_text)),
-            ']' => return Arr (value_arr),
-            ',' => continue,
-            _ => panic!("Unrecognised entry {c}")
-        }
+fn parse_packet(line: &SourceLine) -> Result<RecurPacket, ParseError> {
+    let text = line.text.trim();
+    let mut position = 0;
+    let packet = recur_parse(line, text, &mut position)?;
+    if position < text.len() {
+        return Err(line.error(&text[position..], "unexpected text after the packet"));
     }
-    Arr (value_arr)
+    Ok(packet)
 }
 
-fn compare(a: RecurPacket, b: RecurPacket) -> Ordering{
+// Reads one value starting at `position`, leaving `position` just past it
+fn recur_parse(line: &SourceLine, text: &str, position: &mut usize) -> Result<RecurPacket, ParseError> {
     use RecurPacket::*;
-    use Ordering::*;
-    match (a, b) {
-        (Value(av), Value(bv)) => {
-            return av.cmp(&bv);
-        },
-        (Arr(ar), Arr(br)) => {
-            for i in 0..max(ar.len(), br.len()) {
-                let ag = ar.get(i);
-                let bg = br.get(i);
-
-                match (ag, bg) {
-                    (None, None) => {
-                        // Can't happen, but need to handle it
-                        return Less;
-                    },
-                    (Some(_), None) => {
-                        return Greater;
-                    },
-                    (None, Some(_)) => {
-                        return Less;
+    let bytes = text.as_bytes();
+
+    match bytes.get(*position) {
+        Some(b'[') => {
+            *position += 1;
+            let mut value_arr: Vec<RecurPacket> = Vec::new();
+            if bytes.get(*position) == Some(&b']') {
+                *position += 1;
+                return Ok(Arr (value_arr));
+            }
+            loop {
+                value_arr.push(recur_parse(line, text, position)?);
+                match bytes.get(*position) {
+                    Some(b',') => *position += 1,
+                    Some(b']') => {
+                        *position += 1;
+                        return Ok(Arr (value_arr));
                     },
-                    (Some(ax), Some(bx)) => {
-                        match compare(ax.to_owned(), bx.to_owned()) {
-                            Greater => return Greater,
-                            Less => return Less,
-                            Equal => continue
-                        }
-                    }
+                    _ => return Err(line.error(&text[*position..], "expected `,` or `]`"))
                 }
             }
-            // If equal and of equal length, equal, yeah
-            return Equal
         },
-        (Value(av), Arr(br)) => {
-            return compare(Arr(vec![Value(av)]), Arr(br));
+        Some(c) if c.is_ascii_digit() => {
+            let start = *position;
+            while bytes.get(*position).is_some_and(|c| c.is_ascii_digit()) {
+                *position += 1;
+            }
+            Ok(Value (line.number(&text[start..*position])?))
         },
-        (Arr(ar), Value(bv)) => {
-            return compare(Arr(ar), Arr(vec![Value(bv)]));
+        _ => Err(line.error(&text[*position..], "expected `[` or a number"))
+    }
+}
+
+// Packet order, which isn't an `Ord`: `1` and `[1]` come out equal without being the same packet
+pub fn compare_packets(a: &RecurPacket, b: &RecurPacket) -> Ordering {
+    use RecurPacket::*;
+    match (a, b) {
+        (Value(av), Value(bv)) => av.cmp(bv),
+        (Arr(ar), Arr(br)) => compare_lists(ar, br),
+        (Value(_), Arr(br)) => compare_lists(std::slice::from_ref(a), br),
+        (Arr(ar), Value(_)) => compare_lists(ar, std::slice::from_ref(b))
+    }
+}
+
+fn compare_lists(ar: &[RecurPacket], br: &[RecurPacket]) -> Ordering {
+    use Ordering::*;
+    for i in 0..max(ar.len(), br.len()) {
+        let ag = ar.get(i);
+        let bg = br.get(i);
+
+        match (ag, bg) {
+            (None, None) => {
+                // Can't happen, but need to handle it
+                return Less;
+            },
+            (Some(_), None) => {
+                return Greater;
+            },
+            (None, Some(_)) => {
+                return Less;
+            },
+            (Some(ax), Some(bx)) => {
+                match compare_packets(ax, bx) {
+                    Greater => return Greater,
+                    Less => return Less,
+                    Equal => continue
+                }
+            }
         }
     }
+    // If equal and of equal length, equal, yeah
+    Equal
 }
 
-pub fn evaluate_sorted(input: &String) -> usize {
-    let pairs = read_pairs(input);
+fn sum_sorted_indices(pairs: &[PacketPair]) -> usize {
     pairs.iter().enumerate()
-        .filter(|(_, (a, b))| compare(a.to_owned(), b.to_owned()) != Ordering::Greater)
+        .filter(|(_, (a, b))| compare_packets(a, b) != Ordering::Greater)
         .fold(0, |acc, (v,(_, _))| (v+1)+acc)
 }
 
-pub fn distress_position(input: &String) -> usize {
+fn decoder_key(pairs: &[PacketPair]) -> usize {
     use RecurPacket::*;
     let two_packet = Arr(vec![Arr(vec![Value(2)])]);
     let six_packet = Arr(vec![Arr(vec![Value(6)])]);
 
-    let mut packets = read_all_together(&input);
+    let mut packets: Vec<RecurPacket> = pairs.iter()
+        .flat_map(|(a, b)| [a.clone(), b.clone()])
+        .collect();
     packets.push(two_packet.clone());
     packets.push(six_packet.clone());
-    packets.sort_by(|a, b| compare(a.to_owned(), b.to_owned()));
+    packets.sort_by(compare_packets);
 
     // indexes
     let six_pos = &packets.iter().position(|p| *p == six_packet).unwrap();
@@ -124,45 +165,66 @@ pub fn distress_position(input: &String) -> usize {
     (six_pos+1) * (two_pos+1)
 }
 
+pub fn evaluate_sorted(input: &str) -> Result<usize, ParseError> {
+    Ok(sum_sorted_indices(&read_pairs(input)?))
+}
+
+pub fn distress_position(input: &str) -> Result<usize, ParseError> {
+    Ok(decoder_key(&read_pairs(input)?))
+}
+
+pub struct DistressSignal;
+
+impl Solver for DistressSignal {
+    type Parsed = Vec<PacketPair>;
+
+    fn parse(&self, input: &str) -> Result<Vec<PacketPair>, ParseError> {
+        read_pairs(input)
+    }
+
+    fn part1(&self, pairs: &Vec<PacketPair>) -> Answer {
+        sum_sorted_indices(pairs).into()
+    }
+
+    fn part2(&self, pairs: &Vec<PacketPair>) -> Answer {
+        decoder_key(pairs).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, DistressSignal, 13, 140)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn read_tests() {
-        dbg!(recur_parse_root("[[[9]]]"));
+        use RecurPacket::*;
+        assert_eq!(recur_parse_root("[[[9]]]"), Ok(Arr(vec![Arr(vec![Arr(vec![Value(9)])])])));
+        assert_eq!(recur_parse_root("[[1],4]"), Ok(Arr(vec![Arr(vec![Value(1)]), Value(4)])));
     }
 
     #[test]
-    fn comparison_test() {
-        let input = r"
-[1,1,3,1,1]
-[1,1,5,1,1]
-
-[[1],[2,3,4]]
-[[1],4]
-
-[9]
-[[8,7,6]]
-
-[[4,4],4,4]
-[[4,4],4,4,4]
-
-[7,7,7,7]
-[7,7,7]
-
-[]
-[3]
-
-[[[]]]
-[[]]
+    fn unbalanced_packet() {
+        let error = read_pairs("[1,[2]\n[3]").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+    }
 
-[1,[2,[3,[4,[5,6,7]]]],8,9]
-[1,[2,[3,[4,[5,6,0]]]],8,9]
-        ".to_string();
+    #[test]
+    fn comparison_test() {
+        assert_eq!(evaluate_sorted(EXAMPLE), Ok(13));
 
-        assert_eq!(evaluate_sorted(&input), 13);
+        assert_eq!(distress_position(EXAMPLE), Ok(140));
+    }
 
-        assert_eq!(distress_position(&input), 140);
+    #[test]
+    fn mixed_comparison() {
+        use RecurPacket::*;
+        // Equal in packet order, but still different packets
+        assert_eq!(compare_packets(&Value(1), &Arr(vec![Value(1)])), Ordering::Equal);
+        assert_ne!(Value(1), Arr(vec![Value(1)]));
+        assert_eq!(compare_packets(&Arr(vec![]), &Value(0)), Ordering::Less);
     }
 }
\ No newline at end of file
@@ -1,129 +1,11 @@
-enum Instruction {
-    AddX (i32),
-    Noop
-}
-
-fn parse_instruction(input: &str) -> Instruction {
-    let parts: Vec<&str> = input.split(" ").collect();
-    match parts.get(0).unwrap() {
-        &"addx" => {
-            let value: i32 = parts.get(1).unwrap().parse().unwrap();
-            return Instruction::AddX(value);
-        },
-        &"noop" => {
-            return Instruction::Noop;
-        },
-        _ => panic!("Unrecognised line {input}")
-    }
-}
-
-pub fn sample_at_points(input: &String) -> i32 {
-    let code: Vec<Instruction> = input.trim().lines().map(|l| parse_instruction(l)).collect();
-    let mut x = 1;
-    let mut signal_sum = 0;
-    let mut code_loop = code.iter().cycle();
-    let mut clock = 0;
-
-    let mut check_add = |value: i32, clock: u32| {
-        if clock % 40 == 20 {
-            signal_sum += (clock as i32) * value;
-        }
-    };
-
-    while clock < 221 {
-        let inst = code_loop.next().unwrap();
-        match inst {
-            Instruction::Noop => {
-                clock += 1;
-                check_add(x, clock);
-            },
-            Instruction::AddX(v) => {
-                for _ in 0..2 {
-                    clock += 1;
-                    check_add(x, clock);
-                }
-                x += v;
-            }
-        }
-    }
-    signal_sum
-}
-
-fn process_time(instruction: &Instruction) -> u32 {
-    use Instruction::*;
-    match instruction {
-        Noop => 1,
-        AddX (_) => 2
-    }
-}
-
-struct Process {
-    timer: u32,
-    is_complete: bool,
-    result: i32
-}
-
-impl Process {
-    fn new(instruction: &Instruction) -> Self {
-        Self {
-            timer: process_time(&instruction),
-            result : match instruction {
-                Instruction::Noop => 0,
-                Instruction::AddX(v) => *v
-            },
-            is_complete: false
-        }
-    }
-    fn run(&mut self) -> Option<i32>{
-        self.timer -= 1;
-        if self.timer > 0 {
-            return None;
-        }
-        self.is_complete = true;
-        Some(self.result)
-    }
-}
-
-pub fn get_whole_image(input: &String) {
-    let code: Vec<Instruction> = input.trim().lines().map(|l| parse_instruction(l)).collect();
-    let mut code_loop = code.iter().cycle();
-    let mut x: i32 = 1;
-
-    let mut line: Vec<char> = Vec::new();
-    let mut image: Vec<String> = Vec::new();
-
-    let mut process: Process = Process::new(code_loop.next().unwrap());
-
-    // lines
-    for _ in 0..6 {
-        // cells
-        for i in 0..40 {
-            // Drawing
-            line.push(if (x-i).abs() <= 1 {'#'} else {' '});
-
-            // Processing
-            let r = process.run();
-            if let Some(v) = r {
-                x += v;
-                process = Process::new(code_loop.next().unwrap());
-            }
-        }
-        image.push(line.iter().collect());
-        line = Vec::new();
-    }
+use crate::error::ParseError;
+use crate::parsing::{lines, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
 
-    let final_string = image.join("\n");
-    println!("{final_string}");
-}
+const DAY: u8 = 10;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn long_example() {
-        let input = r"
-addx 15
+pub const EXAMPLE: &str = r"addx 15
 addx -11
 addx 6
 addx -3
@@ -268,9 +150,192 @@ addx -6
 addx -11
 noop
 noop
-noop".to_string();
+noop";
+
+#[derive(Debug)]
+pub enum Instruction {
+    AddX (i32),
+    Noop
+}
 
-        let result = sample_at_points(&input);
+fn parse_instruction(line: &SourceLine) -> Result<Instruction, ParseError> {
+    let parts: Vec<&str> = line.text.trim().split(' ').collect();
+    match line.word(&parts, 0, "an instruction")? {
+        "addx" => {
+            let value: i32 = line.number(line.word(&parts, 1, "a value to add")?)?;
+            Ok(Instruction::AddX(value))
+        },
+        "noop" => Ok(Instruction::Noop),
+        n => Err(line.error(n, format!("unrecognised instruction `{n}`")))
+    }
+}
+
+pub fn read_program(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let code = lines(DAY, input).map(|line| parse_instruction(&line)).collect::<Result<Vec<_>, _>>()?;
+    if code.is_empty() {
+        return Err(ParseError::input(DAY, "the program is empty"));
+    }
+    Ok(code)
+}
+
+fn signal_strength(code: &[Instruction]) -> i32 {
+    let mut x = 1;
+    let mut signal_sum = 0;
+    let mut code_loop = code.iter().cycle();
+    let mut clock = 0;
+
+    let mut check_add = |value: i32, clock: u32| {
+        if clock % 40 == 20 {
+            signal_sum += (clock as i32) * value;
+        }
+    };
+
+    while clock < 221 {
+        let inst = code_loop.next().unwrap();
+        match inst {
+            Instruction::Noop => {
+                clock += 1;
+                check_add(x, clock);
+            },
+            Instruction::AddX(v) => {
+                for _ in 0..2 {
+                    clock += 1;
+                    check_add(x, clock);
+                }
+                x += v;
+            }
+        }
+    }
+    signal_sum
+}
+
+pub fn sample_at_points(input: &str) -> Result<i32, ParseError> {
+    Ok(signal_strength(&read_program(input)?))
+}
+
+fn process_time(instruction: &Instruction) -> u32 {
+    use Instruction::*;
+    match instruction {
+        Noop => 1,
+        AddX (_) => 2
+    }
+}
+
+struct Process {
+    timer: u32,
+    is_complete: bool,
+    result: i32
+}
+
+impl Process {
+    fn new(instruction: &Instruction) -> Self {
+        Self {
+            timer: process_time(instruction),
+            result : match instruction {
+                Instruction::Noop => 0,
+                Instruction::AddX(v) => *v
+            },
+            is_complete: false
+        }
+    }
+    fn run(&mut self) -> Option<i32>{
+        self.timer -= 1;
+        if self.timer > 0 {
+            return None;
+        }
+        self.is_complete = true;
+        Some(self.result)
+    }
+}
+
+fn render_image(code: &[Instruction]) -> Vec<String> {
+    let mut code_loop = code.iter().cycle();
+    let mut x: i32 = 1;
+
+    let mut line: Vec<char> = Vec::new();
+    let mut image: Vec<String> = Vec::new();
+
+    let mut process: Process = Process::new(code_loop.next().unwrap());
+
+    // lines
+    for _ in 0..6 {
+        // cells
+        for i in 0..40 {
+            // Drawing
+            line.push(if (x-i).abs() <= 1 {'#'} else {' '});
+
+            // Processing
+            let r = process.run();
+            if let Some(v) = r {
+                x += v;
+                process = Process::new(code_loop.next().unwrap());
+            }
+        }
+        image.push(line.iter().collect());
+        line = Vec::new();
+    }
+
+    image
+}
+
+pub fn get_whole_image(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(render_image(&read_program(input)?))
+}
+
+pub struct CathodeRayTube;
+
+impl Solver for CathodeRayTube {
+    type Parsed = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Instruction>, ParseError> {
+        read_program(input)
+    }
+
+    fn part1(&self, code: &Vec<Instruction>) -> Answer {
+        signal_strength(code).into()
+    }
+
+    fn part2(&self, code: &Vec<Instruction>) -> Answer {
+        render_image(code).into()
+    }
+}
+
+// Dark pixels are drawn as `.` here to keep the trailing ones visible
+const EXAMPLE_IMAGE: [&str; 6] = [
+    "##..##..##..##..##..##..##..##..##..##..",
+    "###...###...###...###...###...###...###.",
+    "####....####....####....####....####....",
+    "#####.....#####.....#####.....#####.....",
+    "######......######......######......####",
+    "#######.......#######.......#######.....",
+];
+
+fn example_image() -> Vec<String> {
+    EXAMPLE_IMAGE.iter().map(|row| row.replace('.', " ")).collect()
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, CathodeRayTube, 13140, example_image())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_example() {
+        let result = sample_at_points(EXAMPLE).unwrap();
         assert_eq!(result, 13140);
     }
+
+    #[test]
+    fn example_picture() {
+        assert_eq!(get_whole_image(EXAMPLE), Ok(example_image()));
+    }
+
+    #[test]
+    fn bad_value() {
+        let error = read_program("noop\naddx five").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+    }
 }
@@ -0,0 +1,158 @@
+use std::collections::{HashMap, HashSet};
+use crate::error::ParseError;
+use crate::parsing::lines;
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 23;
+
+pub const EXAMPLE: &str = r"....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..";
+
+pub type Coord = (i32, i32);
+pub type World = HashSet<Coord>;
+
+// Each rule is the direction to move and the three spots that have to be empty, starting with north
+const RULES: [(Coord, [Coord; 3]); 4] = [
+    ((0, -1), [(-1, -1), (0, -1), (1, -1)]),
+    ((0, 1), [(-1, 1), (0, 1), (1, 1)]),
+    ((-1, 0), [(-1, -1), (-1, 0), (-1, 1)]),
+    ((1, 0), [(1, -1), (1, 0), (1, 1)]),
+];
+
+pub fn read_elves(input: &str) -> Result<World, ParseError> {
+    let mut world: World = HashSet::new();
+    for line in lines(DAY, input) {
+        let y = line.number as i32 - 1;
+        let text = line.text.trim();
+        for (x, c) in text.char_indices() {
+            match c {
+                '#' => { world.insert((x as i32, y)); },
+                '.' => {},
+                _ => return Err(line.error(&text[x..], format!("`{c}` is not ground, expected `.` or `#`")))
+            }
+        }
+    }
+    if world.is_empty() {
+        return Err(ParseError::input(DAY, "there are no elves"));
+    }
+    Ok(world)
+}
+
+// Runs one round, starting the rule order at `first`. Returns whether anyone moved.
+fn spread(world: &mut World, first: usize) -> bool {
+    let free = |(x, y): &Coord, (dx, dy): &Coord| !world.contains(&(x + dx, y + dy));
+
+    let mut proposals: HashMap<Coord, Vec<Coord>> = HashMap::new();
+    for elf in world.iter() {
+        let alone = RULES.iter().all(|(_, spots)| spots.iter().all(|s| free(elf, s)));
+        if alone {
+            continue;
+        }
+        let rule = (0..4).map(|i| &RULES[(first + i) % 4]).find(|(_, spots)| spots.iter().all(|s| free(elf, s)));
+        if let Some(((dx, dy), _)) = rule {
+            proposals.entry((elf.0 + dx, elf.1 + dy)).or_default().push(*elf);
+        }
+    }
+
+    let mut moved = false;
+    for (target, elves) in proposals {
+        if let [elf] = elves[..] {
+            world.remove(&elf);
+            world.insert(target);
+            moved = true;
+        }
+    }
+    moved
+}
+
+fn empty_ground(world: &World) -> usize {
+    let xs = world.iter().map(|(x, _)| *x);
+    let ys = world.iter().map(|(_, y)| *y);
+    let width = xs.clone().max().unwrap() - xs.min().unwrap() + 1;
+    let height = ys.clone().max().unwrap() - ys.min().unwrap() + 1;
+    (width * height) as usize - world.len()
+}
+
+fn ground_after_ten(world: &World) -> usize {
+    let mut world = world.clone();
+    for round in 0..10 {
+        spread(&mut world, round % 4);
+    }
+    empty_ground(&world)
+}
+
+fn settled_round(world: &World) -> usize {
+    let mut world = world.clone();
+    let mut round = 0;
+    while spread(&mut world, round % 4) {
+        round += 1;
+    }
+    round + 1
+}
+
+pub fn empty_after_ten(input: &str) -> Result<usize, ParseError> {
+    Ok(ground_after_ten(&read_elves(input)?))
+}
+
+pub fn first_still_round(input: &str) -> Result<usize, ParseError> {
+    Ok(settled_round(&read_elves(input)?))
+}
+
+pub struct UnstableDiffusion;
+
+impl Solver for UnstableDiffusion {
+    type Parsed = World;
+
+    fn parse(&self, input: &str) -> Result<World, ParseError> {
+        read_elves(input)
+    }
+
+    fn part1(&self, world: &World) -> Answer {
+        ground_after_ten(world).into()
+    }
+
+    fn part2(&self, world: &World) -> Answer {
+        settled_round(world).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, UnstableDiffusion, 110, 20)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_example() {
+        let mut world = read_elves(".....\n..##.\n..#..\n.....\n..##.\n.....").unwrap();
+        for round in 0..3 {
+            spread(&mut world, round % 4);
+        }
+        let expected = read_elves("..#..\n....#\n#....\n....#\n.....\n..#..").unwrap();
+        assert_eq!(world, expected);
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(empty_after_ten(EXAMPLE), Ok(110));
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(first_still_round(EXAMPLE), Ok(20));
+    }
+
+    #[test]
+    fn bad_ground() {
+        let error = read_elves("..#\n.x.").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+}
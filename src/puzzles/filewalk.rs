@@ -1,6 +1,36 @@
 use std::collections::HashMap;
+use crate::error::ParseError;
+use crate::parsing::{lines, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
 
-type WeightMap = HashMap<String, u32>;
+const DAY: u8 = 7;
+
+pub const EXAMPLE: &str = r"$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+pub type WeightMap = HashMap<String, u32>;
 
 enum Line {
     Move (String),
@@ -10,44 +40,43 @@ enum Line {
     List
 }
 
-fn interpret_line(input: &str) -> Line {
-    let parts: Vec<&str> = input.split(" ").collect();
-    match parts.get(0).expect("Line must be populated") {
-        &"$" => {
-            match parts.get(1).unwrap() {
-                &"cd" => {
-                    if parts.get(2) == Some(&"..") {
-                        return Line::MoveUp;
+fn interpret_line(line: &SourceLine) -> Result<Line, ParseError> {
+    let parts: Vec<&str> = line.text.trim().split(' ').collect();
+    let first = line.word(&parts, 0, "a command or listing")?;
+    match first {
+        "$" => {
+            let command = line.word(&parts, 1, "a command")?;
+            match command {
+                "cd" => {
+                    let directory = line.word(&parts, 2, "a directory to move to")?;
+                    if directory == ".." {
+                        Ok(Line::MoveUp)
                     } else {
-                        return Line::Move(parts.get(2).unwrap().to_string());
+                        Ok(Line::Move(directory.to_string()))
                     }
                 },
-                &"ls" => {
-                    return Line::List;
-                },
-                _ => panic!("Unfamiliar command: {input}")
+                "ls" => Ok(Line::List),
+                _ => Err(line.error(command, format!("unfamiliar command `{command}`")))
             }
         },
-        &"dir" => {
-            return Line::Directory(parts.get(1).unwrap().to_string())
-        },
+        "dir" => Ok(Line::Directory(line.word(&parts, 1, "a directory name")?.to_string())),
         n => {
             if let Ok(size) = n.parse::<u32>() {
-                return Line::File(parts.get(1).unwrap().to_string(), size);
+                Ok(Line::File(line.word(&parts, 1, "a file name")?.to_string(), size))
             } else {
-                panic!("Unfamiliar line: {input}");
+                Err(line.error(n, format!("expected a command, directory or file size, found `{n}`")))
             }
         }
     }
 }
 
-fn build_path_name(address: &Vec<String>, name: &String) -> String{
+fn build_path_name(address: &[String], name: &str) -> String{
     let stack = address.join("/");
     format!("{stack}/{name}")
 }
 
 
-fn traverse_tree(input: &String) -> (WeightMap, WeightMap) {
+pub fn traverse_tree(input: &str) -> Result<(WeightMap, WeightMap), ParseError> {
     let mut weight_stack : Vec<u32> = Vec::new();
     let mut address: Vec<String> = Vec::new();
     let mut weight_sum = 0;
@@ -55,13 +84,13 @@ fn traverse_tree(input: &String) -> (WeightMap, WeightMap) {
     let mut weights: WeightMap = HashMap::new();
     let mut dir_weights: WeightMap = HashMap::new();
 
-    input.trim().lines().for_each(|line| {
-        match interpret_line(line) {
+    for source in lines(DAY, input) {
+        match interpret_line(&source)? {
             Line::List => (), // Do nothing
             Line::File(name, size) => {
                 let full_addr = build_path_name(&address, &name);
                 weights.insert(full_addr, size);
-                weight_sum = weight_sum + size;
+                weight_sum += size;
             },
             Line::Directory(_name) => (), // Do nothing
             Line::Move(directory) => {
@@ -70,39 +99,73 @@ fn traverse_tree(input: &String) -> (WeightMap, WeightMap) {
                 weight_sum = 0;
             },
             Line::MoveUp => {
+                let Some(parent_weight) = weight_stack.pop() else {
+                    return Err(source.error(source.text.trim(), "cannot move up from the outermost directory"));
+                };
                 let full_addr = address.join("/");
                 dir_weights.insert(full_addr, weight_sum);
                 address.pop();
-                weight_sum = weight_sum + weight_stack.pop().unwrap();
+                weight_sum += parent_weight;
             }
         }
-    });
+    }
 
     // Add the last few weights to the map
-    while address.len() > 0 {
+    while !address.is_empty() {
         let full_addr = address.join("/");
         dir_weights.insert(full_addr, weight_sum);
         address.pop();
-        weight_sum = weight_sum + weight_stack.pop().unwrap();
+        weight_sum += weight_stack.pop().expect("Every directory entered saved its parent's weight");
     }
 
-    (weights, dir_weights)
+    if !dir_weights.contains_key("/") {
+        return Err(ParseError::input(DAY, "the listing never starts from the root directory, expected `$ cd /`"));
+    }
+    Ok((weights, dir_weights))
 }
 
-pub fn biggest_small_dirs(input: &String) -> u32 {
-    let (_files, dirs) = traverse_tree(&input);
-
+fn sum_small_dirs(dirs: &WeightMap) -> u32 {
     dirs.values().filter(|v| **v < 100_000).sum()
 }
 
-pub fn smallest_big_dir(input: &String) -> u32 {
-    let (_files, dirs) = traverse_tree(&input);
+fn find_dir_to_delete(dirs: &WeightMap) -> Option<u32> {
+    let top_weight = dirs.get("/")?;
+    let free_space = 70_000_000u32.saturating_sub(*top_weight);
+    let required_space = 30_000_000u32.saturating_sub(free_space);
+
+    dirs.values().filter(|v| **v >= required_space).min().copied()
+}
 
-    let top_weight = dirs.get("/").unwrap();
-    let free_space = 70_000_000 - top_weight;
-    let required_space = 30_000_000 - free_space;
+pub fn biggest_small_dirs(input: &str) -> Result<u32, ParseError> {
+    let (_files, dirs) = traverse_tree(input)?;
+    Ok(sum_small_dirs(&dirs))
+}
 
-    *dirs.values().filter(|v| **v >= required_space).min().unwrap()
+pub fn smallest_big_dir(input: &str) -> Result<Option<u32>, ParseError> {
+    let (_files, dirs) = traverse_tree(input)?;
+    Ok(find_dir_to_delete(&dirs))
+}
+
+pub struct NoSpaceLeft;
+
+impl Solver for NoSpaceLeft {
+    type Parsed = (WeightMap, WeightMap);
+
+    fn parse(&self, input: &str) -> Result<(WeightMap, WeightMap), ParseError> {
+        traverse_tree(input)
+    }
+
+    fn part1(&self, (_files, dirs): &(WeightMap, WeightMap)) -> Answer {
+        sum_small_dirs(dirs).into()
+    }
+
+    fn part2(&self, (_files, dirs): &(WeightMap, WeightMap)) -> Answer {
+        find_dir_to_delete(dirs).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, NoSpaceLeft, 95437, 24933642)
 }
 
 #[cfg(test)]
@@ -111,65 +174,32 @@ mod tests {
 
     #[test]
     fn read_weights() {
-        let input = r"
-$ cd /
-$ ls
-dir a
-14848514 b.txt
-8504156 c.dat
-dir d
-$ cd a
-$ ls
-dir e
-29116 f
-2557 g
-62596 h.lst
-$ cd e
-$ ls
-584 i
-$ cd ..
-$ cd ..
-$ cd d
-$ ls
-4060174 j
-8033020 d.log
-5626152 d.ext
-7214296 k".to_string();
-
-        let (_f, d) = traverse_tree(&input);
+        let (_f, d) = traverse_tree(EXAMPLE).unwrap();
 
         dbg!(d.get("/"));
 
-        assert_eq!(biggest_small_dirs(&input), 95437);
+        assert_eq!(biggest_small_dirs(EXAMPLE), Ok(95437));
     }
 
     #[test]
     fn part_two() {
-        let input = r"
-$ cd /
-$ ls
-dir a
-14848514 b.txt
-8504156 c.dat
-dir d
-$ cd a
-$ ls
-dir e
-29116 f
-2557 g
-62596 h.lst
-$ cd e
-$ ls
-584 i
-$ cd ..
-$ cd ..
-$ cd d
-$ ls
-4060174 j
-8033020 d.log
-5626152 d.ext
-7214296 k".to_string();
+        assert_eq!(smallest_big_dir(EXAMPLE), Ok(Some(24933642)));
+    }
 
-        assert_eq!(smallest_big_dir(&input), 24933642);
+    #[test]
+    fn plenty_of_space() {
+        assert_eq!(smallest_big_dir("$ cd /\n$ ls\n100 a"), Ok(Some(100)));
+    }
+
+    #[test]
+    fn missing_root() {
+        let error = traverse_tree("$ cd a\n$ ls\n100 b").unwrap_err();
+        assert_eq!(error.line, 0);
+    }
+
+    #[test]
+    fn unfamiliar_command() {
+        let error = traverse_tree("$ cd /\n$ rm -rf a").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
     }
 }
\ No newline at end of file
@@ -1,49 +1,254 @@
-type FoodList = Vec<Vec<u32>>;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+use crate::error::{Error, ParseError};
+use crate::parsing::{groups, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
 
-fn read_food(input: &String) -> FoodList {
-    let vectored: Vec<&str> = input.trim().lines().collect();
-    let grouped: Vec<&[&str]> = vectored.split(|s| *s == "").collect();
-    grouped.iter()
+const DAY: u8 = 1;
+
+pub const EXAMPLE: &str = r"1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+pub type FoodList = Vec<Vec<u32>>;
+
+pub fn read_food(input: &str) -> Result<FoodList, ParseError> {
+    let food: FoodList = groups(DAY, input).iter()
         .map(|v| v.iter()
-            .map(|s| s.parse::<u32>().expect("Input must be numeric")).collect()).collect()
+            .map(|line| line.number(line.text.trim())).collect()).collect::<Result<_, _>>()?;
+    if food.is_empty() {
+        return Err(ParseError::input(DAY, "no elves in the input"));
+    }
+    Ok(food)
+}
+
+// Elves' totals, which can outgrow the u32 each item fits in
+fn elf_totals(list: &FoodList) -> impl Iterator<Item = u64> + '_ {
+    list.iter().map(|l| l.iter().copied().map(u64::from).sum())
 }
 
-fn find_max(list: FoodList) -> (usize, u32){
-    let maxes: Vec<(usize, u32)> = list.iter().map(|l| l.iter().sum()).enumerate().collect();
+fn find_max(list: &FoodList) -> (usize, u64){
+    let maxes: Vec<(usize, u64)> = elf_totals(list).enumerate().collect();
 
     *maxes.iter().max_by_key(|(_, v)| v).expect("A maximum should exist")
 }
 
-pub fn find_most_food(input: &String) -> (usize, u32) {
-    let food = read_food(input);
-    find_max(food)
+pub fn find_most_food(input: &str) -> Result<(usize, u64), ParseError> {
+    let food = read_food(input)?;
+    Ok(find_max(&food))
 }
 
-pub fn find_top_three_holders(input: &String) -> (u32, Vec<u32>) {
-    let food = read_food(input);
-    find_top_three(food)
+pub fn find_top_three_holders(input: &str) -> Result<(u64, Vec<u64>), ParseError> {
+    let food = read_food(input)?;
+    Ok(find_top_three(&food))
 }
 
-fn find_top_three(list: FoodList) -> (u32, Vec<u32>){
-    let mut maxes: Vec<u32> = list.iter().map(|l| l.iter().sum()).collect();
+fn find_top_three(list: &FoodList) -> (u64, Vec<u64>){
+    let mut maxes: Vec<u64> = elf_totals(list).collect();
 
     maxes.sort();
-    let n: Vec<u32> = maxes.iter().rev().take(3).map(|v|*v).collect();
+    let n: Vec<u64> = maxes.iter().rev().take(3).copied().collect();
 
-    let sum: u32 = n.iter().sum();
+    let sum: u64 = n.iter().sum();
 
     (sum, n)
 }
 
+// Per-elf totals for answering more than the two puzzle questions. Elves are
+// numbered from zero in the order they appear, as `find_most_food` does.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoodReport {
+    pub totals: Vec<u64>,
+    pub counts: Vec<usize>,
+    // Totals smallest first, for the order statistics
+    sorted: Vec<u64>
+}
+
+impl FoodReport {
+    pub fn new(list: &FoodList) -> Self {
+        let totals: Vec<u64> = elf_totals(list).collect();
+        let counts = list.iter().map(|l| l.len()).collect();
+        let mut sorted = totals.clone();
+        sorted.sort();
+        FoodReport { totals, counts, sorted }
+    }
+
+    pub fn elves(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.totals.iter().sum()
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (!self.totals.is_empty()).then(|| self.total() as f64 / self.elves() as f64)
+    }
+
+    // The middle total, or halfway between the two middle ones
+    pub fn median(&self) -> Option<f64> {
+        let n = self.sorted.len();
+        match n {
+            0 => None,
+            _ if n % 2 == 1 => Some(self.sorted[n / 2] as f64),
+            _ => Some((self.sorted[n / 2 - 1] as f64 + self.sorted[n / 2] as f64) / 2.0)
+        }
+    }
+
+    // Nearest rank: the smallest total that at least `p` percent of elves are carrying no more than
+    pub fn percentile(&self, p: f64) -> Option<u64> {
+        if self.sorted.is_empty() || !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+        let rank = (p / 100.0 * self.sorted.len() as f64).ceil() as usize;
+        Some(self.sorted[rank.max(1) - 1])
+    }
+
+    // The `k` elves carrying the most, as (elf, total). Equal totals keep input order.
+    pub fn top(&self, k: usize) -> Vec<(usize, u64)> {
+        let mut ranked: Vec<(usize, u64)> = self.totals.iter().copied().enumerate().collect();
+        ranked.sort_by(|(a, x), (b, y)| y.cmp(x).then(a.cmp(b)));
+        ranked.truncate(k);
+        ranked
+    }
+
+    // Like `top`, but also keeps anyone tied with the last place
+    pub fn top_with_ties(&self, k: usize) -> Vec<(usize, u64)> {
+        match self.top(k).last() {
+            Some((_, cutoff)) => self.at_least(*cutoff),
+            None => Vec::new()
+        }
+    }
+
+    // Every elf carrying at least `threshold` calories, most first
+    pub fn at_least(&self, threshold: u64) -> Vec<(usize, u64)> {
+        let mut ranked = self.top(self.elves());
+        ranked.retain(|(_, total)| *total >= threshold);
+        ranked
+    }
+}
+
+pub fn food_report(input: &str) -> Result<FoodReport, ParseError> {
+    Ok(FoodReport::new(&read_food(input)?))
+}
+
+// Reads the inventory a line at a time and yields each elf's total as soon as their
+// group ends, so the input never has to fit in memory
+pub struct ElfTotals<R> {
+    reader: R,
+    line: String,
+    number: usize,
+    done: bool
+}
+
+pub fn stream_totals<R: BufRead>(reader: R) -> ElfTotals<R> {
+    ElfTotals { reader, line: String::new(), number: 0, done: false }
+}
+
+impl<R: BufRead> ElfTotals<R> {
+    fn next_group(&mut self) -> Result<Option<u64>, Error> {
+        let mut total: Option<u64> = None;
+        loop {
+            self.line.clear();
+            let read = self.reader.read_line(&mut self.line)
+                .map_err(|source| Error::Io { path: "<stream>".to_string(), source })?;
+            if read == 0 {
+                return Ok(total);
+            }
+            self.number += 1;
+
+            let line = SourceLine { day: DAY, number: self.number, text: self.line.trim_end_matches(['\n', '\r']) };
+            let token = line.text.trim();
+            if token.is_empty() {
+                match total {
+                    Some(_) => return Ok(total),
+                    None => continue
+                }
+            }
+            let calories: u64 = line.number(token)?;
+            let sum = total.unwrap_or(0).checked_add(calories)
+                .ok_or_else(|| line.error(token, "this elf is carrying too many calories to count"))?;
+            total = Some(sum);
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for ElfTotals<R> {
+    type Item = Result<u64, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let group = self.next_group();
+        self.done = !matches!(group, Ok(Some(_)));
+        group.transpose()
+    }
+}
+
+// The `k` elves carrying the most as (elf, total), most first and equal totals in input
+// order, keeping only `k` elves in memory at a time
+pub fn largest_totals<R: BufRead>(reader: R, k: usize) -> Result<Vec<(usize, u64)>, Error> {
+    // Min-heap on the ranking, so the weakest of the kept elves is the one to go. It grows
+    // as elves turn up rather than being sized for `k`, which can be far more than there are.
+    let mut kept: BinaryHeap<Reverse<(u64, Reverse<usize>)>> = BinaryHeap::new();
+    let mut elves = 0;
+    for (elf, total) in stream_totals(reader).enumerate() {
+        kept.push(Reverse((total?, Reverse(elf))));
+        if kept.len() > k {
+            kept.pop();
+        }
+        elves += 1;
+    }
+    if elves == 0 {
+        return Err(ParseError::input(DAY, "no elves in the input").into());
+    }
+    Ok(kept.into_sorted_vec().into_iter().map(|Reverse((total, Reverse(elf)))| (elf, total)).collect())
+}
+
+pub struct CalorieCounting;
+
+impl Solver for CalorieCounting {
+    type Parsed = FoodList;
+
+    fn parse(&self, input: &str) -> Result<FoodList, ParseError> {
+        read_food(input)
+    }
+
+    fn part1(&self, food: &FoodList) -> Answer {
+        let (_, most) = find_max(food);
+        most.into()
+    }
+
+    fn part2(&self, food: &FoodList) -> Answer {
+        let (total, _) = find_top_three(food);
+        total.into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, CalorieCounting, 24000, 45000)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn sample_part_one() {
-        let sample = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000"
-        .to_string();
-
-        let (number, value) = find_most_food(&sample);
+        let (number, value) = find_most_food(EXAMPLE).unwrap();
 
         assert_eq!(number, 3);
         assert_eq!(value, 24000);
@@ -51,15 +256,96 @@ mod tests {
 
     #[test]
     fn sample_part_two() {
-        let sample = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000"
-        .to_string();
-
-        let (total, top_three) = find_top_three_holders(&sample);
+        let (total, top_three) = find_top_three_holders(EXAMPLE).unwrap();
 
         assert_eq!(total, 45000);
-        assert_eq!(top_three.get(0), Some(&24000));
+        assert_eq!(top_three.first(), Some(&24000));
         assert_eq!(top_three.get(1), Some(&11000));
         assert_eq!(top_three.get(2), Some(&10000));
 
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn past_u32() {
+        let food = read_food("4000000000\n4000000000\n\n1").unwrap();
+
+        assert_eq!(find_max(&food), (0, 8000000000));
+        assert_eq!(find_top_three(&food).0, 8000000001);
+    }
+
+    #[test]
+    fn report_statistics() {
+        let report = food_report(EXAMPLE).unwrap();
+
+        assert_eq!(report.totals, vec![6000, 4000, 11000, 24000, 10000]);
+        assert_eq!(report.counts, vec![3, 1, 2, 3, 1]);
+        assert_eq!(report.total(), 55000);
+        assert_eq!(report.mean(), Some(11000.0));
+        assert_eq!(report.median(), Some(10000.0));
+        assert_eq!(report.percentile(0.0), Some(4000));
+        assert_eq!(report.percentile(40.0), Some(6000));
+        assert_eq!(report.percentile(100.0), Some(24000));
+        assert_eq!(report.percentile(101.0), None);
+    }
+
+    #[test]
+    fn report_ties() {
+        let report = food_report("5\n\n2\n3\n\n4\n1\n\n3\n\n1").unwrap();
+
+        assert_eq!(report.median(), Some(5.0));
+        assert_eq!(report.top(2), vec![(0, 5), (1, 5)]);
+        assert_eq!(report.top_with_ties(2), vec![(0, 5), (1, 5), (2, 5)]);
+        assert_eq!(report.at_least(5).len(), 3);
+        assert_eq!(report.top(10).len(), 5);
+    }
+
+    #[test]
+    fn report_past_u32() {
+        let report = food_report("2000000000\n2000000000\n\n3000000000").unwrap();
+
+        assert_eq!(report.totals, vec![4000000000, 3000000000]);
+        assert_eq!(report.total(), 7000000000);
+        assert_eq!(report.top(1), vec![(0, 4000000000)]);
+        assert_eq!(report.at_least(3500000000).len(), 1);
+    }
+
+    #[test]
+    fn streaming_totals() {
+        let totals: Vec<u64> = stream_totals(EXAMPLE.as_bytes()).collect::<Result<_, _>>().unwrap();
+        assert_eq!(totals, vec![6000, 4000, 11000, 24000, 10000]);
+
+        // Past what a u32 can hold, with extra blank lines and Windows line endings
+        let big = "\r\n\r\n4000000000\r\n4000000000\r\n\r\n\r\n1\r\n";
+        let totals: Vec<u64> = stream_totals(big.as_bytes()).collect::<Result<_, _>>().unwrap();
+        assert_eq!(totals, vec![8_000_000_000, 1]);
+    }
+
+    #[test]
+    fn streaming_top() {
+        assert_eq!(largest_totals(EXAMPLE.as_bytes(), 3).unwrap(), vec![(3, 24000), (2, 11000), (4, 10000)]);
+        let tied = "5\n\n2\n3\n\n4\n1\n\n3\n\n1";
+        assert_eq!(largest_totals(tied.as_bytes(), 2).unwrap(), vec![(0, 5), (1, 5)]);
+        assert_eq!(largest_totals(tied.as_bytes(), 0).unwrap(), vec![]);
+        assert_eq!(largest_totals(tied.as_bytes(), usize::MAX).unwrap().len(), 5);
+        assert!(largest_totals("\n\n".as_bytes(), 3).is_err());
+    }
+
+    #[test]
+    fn streaming_bad_calories() {
+        let mut totals = stream_totals("1000\n2000\n\n30x0\n\n5".as_bytes());
+        assert_eq!(totals.next().unwrap().unwrap(), 3000);
+        let Some(Err(Error::Parse(error))) = totals.next() else { panic!("expected a parse error") };
+        assert_eq!((error.line, error.column), (4, 1));
+        assert!(totals.next().is_none());
+    }
+
+    #[test]
+    fn bad_calories() {
+        let sample = "1000\n2000\n\n30x0";
+
+        let error = read_food(sample).unwrap_err();
+
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.text, "30x0");
+    }
+}
@@ -0,0 +1,190 @@
+use crate::error::ParseError;
+use crate::parsing::{lines, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 19;
+
+pub const EXAMPLE: &str = r"Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+// Amounts of ore, clay, obsidian and geodes, in that order
+pub type Materials = [u32; 4];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blueprint {
+    pub id: u32,
+    // What each kind of robot costs to build, indexed like Materials
+    pub costs: [Materials; 4]
+}
+
+fn read_blueprint(line: &SourceLine) -> Result<Blueprint, ParseError> {
+    let words: Vec<&str> = line.text.split_whitespace().collect();
+    let number = |index: usize, expected: &str| -> Result<u32, ParseError> {
+        let word = line.word(&words, index, expected)?;
+        line.number(word.trim_end_matches(':'))
+    };
+
+    let id = number(1, "a blueprint number")?;
+    let ore = number(6, "the ore robot's cost")?;
+    let clay = number(12, "the clay robot's cost")?;
+    let obsidian = [number(18, "the obsidian robot's ore cost")?, number(21, "the obsidian robot's clay cost")?];
+    let geode = [number(27, "the geode robot's ore cost")?, number(30, "the geode robot's obsidian cost")?];
+
+    Ok(Blueprint {
+        id,
+        costs: [
+            [ore, 0, 0, 0],
+            [clay, 0, 0, 0],
+            [obsidian[0], obsidian[1], 0, 0],
+            [geode[0], 0, geode[1], 0]
+        ]
+    })
+}
+
+pub fn read_blueprints(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    let blueprints: Vec<Blueprint> = lines(DAY, input).map(|line| read_blueprint(&line)).collect::<Result<_, _>>()?;
+    if blueprints.is_empty() {
+        return Err(ParseError::input(DAY, "there are no blueprints"));
+    }
+    Ok(blueprints)
+}
+
+// Only one robot can be built a minute, so there's no use making more of one kind
+// than the most of that material any robot needs
+fn robot_limits(blueprint: &Blueprint) -> Materials {
+    let most = |m: usize| blueprint.costs.iter().map(|c| c[m]).max().unwrap_or(0);
+    [most(ORE), most(CLAY), most(OBSIDIAN), u32::MAX]
+}
+
+// Rather than stepping minute by minute, each branch picks the next robot to build and
+// waits until it can afford it
+fn search(
+    blueprint: &Blueprint,
+    limits: &Materials,
+    best: &mut u32,
+    minutes: u32,
+    robots: Materials,
+    stock: Materials
+) {
+    // What we'd finish with if nothing else were built
+    let idle = stock[GEODE] + robots[GEODE] * minutes;
+    *best = (*best).max(idle);
+
+    // Even a new geode robot every remaining minute couldn't beat the best so far
+    if idle + minutes * minutes.saturating_sub(1) / 2 <= *best {
+        return;
+    }
+
+    for robot in (ORE..=GEODE).rev() {
+        if robots[robot] >= limits[robot] {
+            continue;
+        }
+
+        let cost = blueprint.costs[robot];
+        let wait = (ORE..GEODE).map(|m| {
+            if cost[m] <= stock[m] {
+                Some(0)
+            } else if robots[m] == 0 {
+                None
+            } else {
+                Some((cost[m] - stock[m]).div_ceil(robots[m]))
+            }
+        }).collect::<Option<Vec<u32>>>().and_then(|w| w.into_iter().max());
+
+        let Some(wait) = wait else { continue };
+        // Building takes a minute too, and a robot finished on the last minute is no use
+        if wait + 1 >= minutes {
+            continue;
+        }
+
+        let elapsed = wait + 1;
+        let mut new_stock = stock;
+        for m in ORE..=GEODE {
+            new_stock[m] = stock[m] + robots[m] * elapsed - cost[m];
+        }
+        let mut new_robots = robots;
+        new_robots[robot] += 1;
+
+        search(blueprint, limits, best, minutes - elapsed, new_robots, new_stock);
+    }
+}
+
+pub fn most_geodes(blueprint: &Blueprint, minutes: u32) -> u32 {
+    let mut best = 0;
+    search(blueprint, &robot_limits(blueprint), &mut best, minutes, [1, 0, 0, 0], [0; 4]);
+    best
+}
+
+fn quality_levels(blueprints: &[Blueprint]) -> u32 {
+    blueprints.iter().map(|b| b.id * most_geodes(b, 24)).sum()
+}
+
+fn first_three_product(blueprints: &[Blueprint]) -> u32 {
+    blueprints.iter().take(3).map(|b| most_geodes(b, 32)).product()
+}
+
+pub fn quality_level_sum(input: &str) -> Result<u32, ParseError> {
+    Ok(quality_levels(&read_blueprints(input)?))
+}
+
+pub fn longer_geode_product(input: &str) -> Result<u32, ParseError> {
+    Ok(first_three_product(&read_blueprints(input)?))
+}
+
+pub struct NotEnoughMinerals;
+
+impl Solver for NotEnoughMinerals {
+    type Parsed = Vec<Blueprint>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Blueprint>, ParseError> {
+        read_blueprints(input)
+    }
+
+    fn part1(&self, blueprints: &Vec<Blueprint>) -> Answer {
+        quality_levels(blueprints).into()
+    }
+
+    fn part2(&self, blueprints: &Vec<Blueprint>) -> Answer {
+        first_three_product(blueprints).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, NotEnoughMinerals, 33, 56 * 62)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reading() {
+        let blueprints = read_blueprints(EXAMPLE).unwrap();
+        assert_eq!(blueprints[1].id, 2);
+        assert_eq!(blueprints[1].costs[GEODE], [3, 0, 12, 0]);
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(quality_level_sum(EXAMPLE), Ok(33));
+    }
+
+    #[test]
+    fn part_two() {
+        let blueprints = read_blueprints(EXAMPLE).unwrap();
+        assert_eq!(most_geodes(&blueprints[0], 32), 56);
+        assert_eq!(most_geodes(&blueprints[1], 32), 62);
+    }
+
+    #[test]
+    fn truncated_blueprint() {
+        let error = read_blueprints("Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs").unwrap_err();
+        assert_eq!(error.reason, "expected the clay robot's cost");
+    }
+}
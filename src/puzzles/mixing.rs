@@ -0,0 +1,144 @@
+use crate::error::ParseError;
+use crate::parsing::lines;
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 20;
+
+pub const EXAMPLE: &str = r"1
+2
+-3
+3
+-2
+0
+4";
+
+const DECRYPTION_KEY: i64 = 811589153;
+
+pub type File = Vec<i64>;
+
+pub fn read_file(input: &str) -> Result<File, ParseError> {
+    let file: File = lines(DAY, input).map(|line| line.number(line.text.trim())).collect::<Result<_, _>>()?;
+    if !file.contains(&0) {
+        return Err(ParseError::input(DAY, "the file has no 0 to measure the grove coordinates from"));
+    }
+    Ok(file)
+}
+
+// Values can repeat, so the list being shuffled holds indexes into the original file.
+// Each value moves as if multiplied by `key`, without the product ever being formed
+fn mix(file: &File, key: i64, rounds: usize) -> File {
+    let mut order: Vec<usize> = (0..file.len()).collect();
+    // Moving all the way round the others brings a number back where it started
+    let others = file.len() as i64 - 1;
+
+    for _ in 0..rounds {
+        for (index, value) in file.iter().enumerate() {
+            if others == 0 {
+                break;
+            }
+            let from = order.iter().position(|i| *i == index).expect("Every index is in the order");
+            order.remove(from);
+            let shift = (value % others) * (key % others);
+            let to = (from as i64 + shift).rem_euclid(others) as usize;
+            order.insert(to, index);
+        }
+    }
+
+    order.iter().map(|i| file[*i]).collect()
+}
+
+// None if the keyed sum doesn't fit in an i64
+fn grove_coordinates(mixed: &File, key: i64) -> Option<i64> {
+    let zero = mixed.iter().position(|v| *v == 0).expect("read_file checks for a 0");
+    let sum: i128 = [1000, 2000, 3000].iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()] as i128 * key as i128)
+        .sum();
+    i64::try_from(sum).ok()
+}
+
+fn decrypt(file: &File) -> Option<i64> {
+    grove_coordinates(&mix(file, 1, 1), 1)
+}
+
+fn decrypt_with_key(file: &File) -> Option<i64> {
+    grove_coordinates(&mix(file, DECRYPTION_KEY, 10), DECRYPTION_KEY)
+}
+
+pub fn grove_sum(input: &str) -> Result<Option<i64>, ParseError> {
+    Ok(decrypt(&read_file(input)?))
+}
+
+pub fn keyed_grove_sum(input: &str) -> Result<Option<i64>, ParseError> {
+    Ok(decrypt_with_key(&read_file(input)?))
+}
+
+pub struct GrovePositioningSystem;
+
+impl Solver for GrovePositioningSystem {
+    type Parsed = File;
+
+    fn parse(&self, input: &str) -> Result<File, ParseError> {
+        read_file(input)
+    }
+
+    fn part1(&self, file: &File) -> Answer {
+        decrypt(file).into()
+    }
+
+    fn part2(&self, file: &File) -> Answer {
+        decrypt_with_key(file).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, GrovePositioningSystem, 3, 1623178306i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The puzzle prints lists starting wherever it likes, so compare them from the 0
+    fn from_zero(list: &File) -> File {
+        let zero = list.iter().position(|v| *v == 0).unwrap();
+        list[zero..].iter().chain(&list[..zero]).copied().collect()
+    }
+
+    #[test]
+    fn one_round() {
+        let file = read_file(EXAMPLE).unwrap();
+        assert_eq!(from_zero(&mix(&file, 1, 1)), vec![0, 3, -2, 1, 2, -3, 4]);
+    }
+
+    #[test]
+    fn duplicates() {
+        // Each 1 moves on its own turn rather than both moving together
+        assert_eq!(from_zero(&mix(&vec![1, 1, 0], 1, 1)), vec![0, 1, 1]);
+        assert_eq!(from_zero(&mix(&vec![2, 0, 2, 5], 1, 1)), vec![0, 5, 2, 2]);
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(grove_sum(EXAMPLE), Ok(Some(3)));
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(keyed_grove_sum(EXAMPLE), Ok(Some(1623178306)));
+    }
+
+    #[test]
+    fn huge_values() {
+        let input = "0\n20000000000\n-3";
+        assert_eq!(grove_sum(&input.replace("20000000000", &i64::MAX.to_string())), Ok(Some(i64::MAX - 3)));
+        // Keyed, the coordinates sum past what an i64 holds
+        assert_eq!(keyed_grove_sum(input), Ok(None));
+    }
+
+    #[test]
+    fn no_zero() {
+        let error = read_file("1\n2\n3").unwrap_err();
+        assert_eq!(error.line, 0);
+    }
+}
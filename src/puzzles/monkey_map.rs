@@ -0,0 +1,375 @@
+use std::collections::{HashMap, VecDeque};
+use crate::error::ParseError;
+use crate::parsing::{groups, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 22;
+
+pub const EXAMPLE: &str = r"        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
+// Facing values as the password counts them: right, down, left, up
+const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    Forward(u32),
+    Left,
+    Right
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Board {
+    // Rows of `.`, `#` and ` ` for the void, not all the same length
+    tiles: Vec<Vec<u8>>,
+    path: Vec<Step>
+}
+
+// x, y and facing
+type Position = (i32, i32, usize);
+
+type Vector = [i32; 3];
+
+// How a face of the net sits on the cube: which way is out, and where its
+// right and down directions on the net point
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Frame {
+    normal: Vector,
+    right: Vector,
+    down: Vector
+}
+
+fn neg(v: Vector) -> Vector {
+    v.map(|a| -a)
+}
+
+fn dot(a: Vector, b: Vector) -> i32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn add(a: Vector, b: Vector) -> Vector {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(v: Vector, n: i32) -> Vector {
+    v.map(|a| a * n)
+}
+
+fn read_path(line: &SourceLine) -> Result<Vec<Step>, ParseError> {
+    let text = line.text.trim();
+    let mut path: Vec<Step> = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        if c.is_ascii_digit() {
+            continue;
+        }
+        if start < i {
+            path.push(Step::Forward(line.number(&text[start..i])?));
+        }
+        path.push(match c {
+            'L' => Step::Left,
+            'R' => Step::Right,
+            _ => return Err(line.error(&text[i..], format!("`{c}` is not a turn, expected `L` or `R`")))
+        });
+        start = i + 1;
+    }
+    if start < text.len() {
+        path.push(Step::Forward(line.number(&text[start..])?));
+    }
+    Ok(path)
+}
+
+pub fn read_board(input: &str) -> Result<Board, ParseError> {
+    let grouped = groups(DAY, input);
+    let [map, path] = &grouped[..] else {
+        return Err(ParseError::input(DAY, "expected the map, a blank line, then the path"));
+    };
+
+    for line in map {
+        if let Some(i) = line.text.find(|c| !" .#".contains(c)) {
+            return Err(line.error(&line.text[i..], "expected `.`, `#` or a space"));
+        }
+    }
+    let tiles: Vec<Vec<u8>> = map.iter().map(|l| l.text.trim_end().as_bytes().to_vec()).collect();
+    if !tiles[0].contains(&b'.') {
+        return Err(map[0].error(map[0].text, "there is nowhere to start on the top row"));
+    }
+
+    Ok(Board { tiles, path: read_path(&path[0])? })
+}
+
+impl Board {
+    fn tile(&self, x: i32, y: i32) -> Option<u8> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+        self.tiles.get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+            .filter(|t| *t != b' ')
+    }
+
+    fn start(&self) -> Position {
+        let x = self.tiles[0].iter().position(|t| *t == b'.').expect("read_board checks for a start");
+        (x as i32, 0, 0)
+    }
+
+    fn walk(&self, wrap: impl Fn(Position) -> Position) -> usize {
+        let (mut x, mut y, mut facing) = self.start();
+
+        for step in &self.path {
+            match step {
+                Step::Left => facing = (facing + 3) % 4,
+                Step::Right => facing = (facing + 1) % 4,
+                Step::Forward(n) => {
+                    for _ in 0..*n {
+                        let (dx, dy) = DIRECTIONS[facing];
+                        let next = match self.tile(x + dx, y + dy) {
+                            Some(_) => (x + dx, y + dy, facing),
+                            None => wrap((x, y, facing))
+                        };
+                        if self.tile(next.0, next.1) == Some(b'#') {
+                            break;
+                        }
+                        (x, y, facing) = next;
+                    }
+                }
+            }
+        }
+
+        1000 * (y as usize + 1) + 4 * (x as usize + 1) + facing
+    }
+
+    // Walking off an edge of the flat map comes back on the far side of the row or column
+    fn wrap_flat(&self, (x, y, facing): Position) -> Position {
+        let (dx, dy) = DIRECTIONS[facing];
+        let (mut x, mut y) = (x, y);
+        while self.tile(x - dx, y - dy).is_some() {
+            x -= dx;
+            y -= dy;
+        }
+        (x, y, facing)
+    }
+
+    fn face_size(&self) -> Option<usize> {
+        let area = self.tiles.iter().flatten().filter(|t| **t != b' ').count();
+        let size = (1..=area).find(|s| 6 * s * s >= area)?;
+        (6 * size * size == area).then_some(size)
+    }
+
+    // Folds the net up around the first face, working out each face's frame from its
+    // neighbour's as it goes
+    fn fold(&self, size: usize) -> Option<HashMap<(i32, i32), Frame>> {
+        let n = size as i32;
+        let start = self.start();
+        let first = (start.0 / n, 0);
+        let mut frames: HashMap<(i32, i32), Frame> = HashMap::new();
+        frames.insert(first, Frame { normal: [0, 0, -1], right: [1, 0, 0], down: [0, 1, 0] });
+        let mut queue: VecDeque<(i32, i32)> = VecDeque::from([first]);
+
+        while let Some((fx, fy)) = queue.pop_front() {
+            let f = frames[&(fx, fy)];
+            let neighbours = [
+                ((fx + 1, fy), Frame { normal: f.right, right: neg(f.normal), down: f.down }),
+                ((fx, fy + 1), Frame { normal: f.down, right: f.right, down: neg(f.normal) }),
+                ((fx - 1, fy), Frame { normal: neg(f.right), right: f.normal, down: f.down }),
+                ((fx, fy - 1), Frame { normal: neg(f.down), right: f.right, down: f.normal })
+            ];
+            for (face, frame) in neighbours {
+                if self.tile(face.0 * n, face.1 * n).is_some() && !frames.contains_key(&face) {
+                    frames.insert(face, frame);
+                    queue.push_back(face);
+                }
+            }
+        }
+
+        // A net that doesn't fold has two faces landing on the same side
+        let mut normals: Vec<Vector> = frames.values().map(|f| f.normal).collect();
+        normals.sort();
+        normals.dedup();
+        if frames.len() != 6 || normals.len() != 6 {
+            return None;
+        }
+
+        // Only the faces' corners were looked at, so check the tiles really make up those faces
+        let full = frames.keys().all(|(fx, fy)| {
+            (0..n).all(|dy| (0..n).all(|dx| self.tile(fx * n + dx, fy * n + dy).is_some()))
+        });
+        let covered = self.tiles.iter().enumerate().all(|(y, row)| {
+            row.iter().enumerate()
+                .filter(|(_, t)| **t != b' ')
+                .all(|(x, _)| frames.contains_key(&(x as i32 / n, y as i32 / n)))
+        });
+        (full && covered).then_some(frames)
+    }
+
+    // Steps over the edge of a face onto the face it meets on the cube. Positions are
+    // tile centres in 3D, doubled so they stay whole numbers.
+    fn wrap_cube(&self, frames: &HashMap<(i32, i32), Frame>, size: usize, (x, y, facing): Position) -> Position {
+        let n = size as i32;
+        let from = frames[&(x / n, y / n)];
+        let (col, row) = (x % n, y % n);
+        let moving = match facing {
+            0 => from.right,
+            1 => from.down,
+            2 => neg(from.right),
+            _ => neg(from.down)
+        };
+
+        let here = add(scale(from.normal, n), add(scale(from.right, 2 * col - (n - 1)), scale(from.down, 2 * row - (n - 1))));
+        let there = add(here, add(moving, neg(from.normal)));
+
+        let (&(gx, gy), to) = frames.iter().find(|(_, f)| f.normal == moving).expect("A folded cube has every side");
+        let heading = neg(from.normal);
+        let facing = [to.right, to.down, neg(to.right), neg(to.down)].iter()
+            .position(|d| *d == heading)
+            .expect("Moving off an edge heads into the next face");
+
+        let col = (dot(there, to.right) + n - 1) / 2;
+        let row = (dot(there, to.down) + n - 1) / 2;
+        (gx * n + col, gy * n + row, facing)
+    }
+}
+
+fn flat_password(board: &Board) -> usize {
+    board.walk(|p| board.wrap_flat(p))
+}
+
+fn cube_password(board: &Board) -> Option<usize> {
+    let size = board.face_size()?;
+    let frames = board.fold(size)?;
+    Some(board.walk(|p| board.wrap_cube(&frames, size, p)))
+}
+
+pub fn final_password(input: &str) -> Result<usize, ParseError> {
+    Ok(flat_password(&read_board(input)?))
+}
+
+pub fn cube_final_password(input: &str) -> Result<Option<usize>, ParseError> {
+    Ok(cube_password(&read_board(input)?))
+}
+
+pub struct MonkeyMap;
+
+impl Solver for MonkeyMap {
+    type Parsed = Board;
+
+    fn parse(&self, input: &str) -> Result<Board, ParseError> {
+        read_board(input)
+    }
+
+    fn part1(&self, board: &Board) -> Answer {
+        flat_password(board).into()
+    }
+
+    fn part2(&self, board: &Board) -> Answer {
+        cube_password(board).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, MonkeyMap, 6032, 5031)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::Puzzle;
+
+    #[test]
+    fn reading_path() {
+        let board = read_board(EXAMPLE).unwrap();
+        assert_eq!(board.path[..4], [Step::Forward(10), Step::Right, Step::Forward(5), Step::Left]);
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(final_password(EXAMPLE), Ok(6032));
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(cube_final_password(EXAMPLE), Ok(Some(5031)));
+    }
+
+    #[test]
+    fn other_net() {
+        // The real inputs use this layout instead. Walking right from the top-left face
+        // crosses two faces and comes back round to where it started.
+        let row = |faces: &str| faces.replace('X', "..").replace('_', "  ");
+        let net = [row("_XX"), row("_X"), row("XX"), row("X")].iter()
+            .flat_map(|r| [r.clone(), r.clone()])
+            .collect::<Vec<String>>()
+            .join("\n");
+        let board = read_board(&format!("{net}\n\n8")).unwrap();
+
+        assert_eq!(board.face_size(), Some(2));
+        assert_eq!(cube_password(&board), Some(1000 + 4 * 3));
+        assert_loops(&board);
+    }
+
+    // With no walls, going straight for four faces' worth of steps gets back to the start
+    fn assert_loops(board: &Board) {
+        let size = board.face_size().unwrap();
+        let frames = board.fold(size).unwrap();
+        for (y, row) in board.tiles.iter().enumerate() {
+            for x in (0..row.len()).filter(|x| row[*x] != b' ') {
+                for facing in 0..4 {
+                    let start = (x as i32, y as i32, facing);
+                    let mut p = start;
+                    for _ in 0..4 * size {
+                        let (dx, dy) = DIRECTIONS[p.2];
+                        p = match board.tile(p.0 + dx, p.1 + dy) {
+                            Some(_) => (p.0 + dx, p.1 + dy, p.2),
+                            None => board.wrap_cube(&frames, size, p)
+                        };
+                    }
+                    assert_eq!(p, start);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn example_loops() {
+        assert_loops(&read_board(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn unfoldable() {
+        // Six faces in a row wrap round onto themselves
+        let board = read_board("......\n\n1").unwrap();
+        assert_eq!(board.face_size(), Some(1));
+        assert_eq!(cube_password(&board), None);
+    }
+
+    #[test]
+    fn misaligned_faces() {
+        // Enough tiles for six faces, and the corners fold, but the tiles spill off them
+        let input = "  ..\n ...\n......\n......\n  ..\n  ..\n  ..\n  . \n\nR1R2";
+        let board = read_board(input).unwrap();
+        assert_eq!(board.face_size(), Some(2));
+        assert_eq!(cube_password(&board), None);
+        assert_eq!(MonkeyMap.solve(input).unwrap().part2, Answer::Unsolved);
+    }
+
+    #[test]
+    fn bad_turn() {
+        let error = read_board("..\n..\n\n10R5X").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 5));
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use crate::error::ParseError;
+use crate::parsing::{lines, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 21;
+
+pub const EXAMPLE: &str = r"root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Add,
+    Subtract,
+    Multiply,
+    Divide
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(i64),
+    // What the human was going to yell, which part 2 treats as unknown
+    Human(i64),
+    Operation(Box<Expr>, Op, Box<Expr>)
+}
+
+enum Job<'a> {
+    Yell(i64),
+    Wait(&'a str, Op, &'a str)
+}
+
+impl Op {
+    fn apply(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Op::Add => a.checked_add(b),
+            Op::Subtract => a.checked_sub(b),
+            Op::Multiply => a.checked_mul(b),
+            Op::Divide => a.checked_div(b)
+        }
+    }
+}
+
+impl Expr {
+    fn value(&self, human_known: bool) -> Option<i64> {
+        match self {
+            Expr::Number(n) => Some(*n),
+            Expr::Human(n) => human_known.then_some(*n),
+            Expr::Operation(l, op, r) => op.apply(l.value(human_known)?, r.value(human_known)?)
+        }
+    }
+
+    pub fn evaluate(&self) -> Option<i64> {
+        self.value(true)
+    }
+
+    // The value of a branch that doesn't depend on the human
+    fn constant(&self) -> Option<i64> {
+        self.value(false)
+    }
+
+    // Works back down the tree from the value this expression has to take, undoing each
+    // operation on the branch the human is in
+    pub fn solve(&self, target: i64) -> Option<i64> {
+        let exact = |a: i64, b: i64| (b != 0 && a % b == 0).then(|| a / b);
+        match self {
+            Expr::Human(_) => Some(target),
+            Expr::Number(_) => None,
+            Expr::Operation(l, op, r) => match r.constant() {
+                Some(right) => {
+                    let wanted = match op {
+                        Op::Add => target.checked_sub(right)?,
+                        Op::Subtract => target.checked_add(right)?,
+                        Op::Multiply => exact(target, right)?,
+                        Op::Divide => target.checked_mul(right)?
+                    };
+                    l.solve(wanted)
+                },
+                None => {
+                    let left = l.constant()?;
+                    let wanted = match op {
+                        Op::Add => target.checked_sub(left)?,
+                        Op::Subtract => left.checked_sub(target)?,
+                        Op::Multiply => exact(target, left)?,
+                        Op::Divide => exact(left, target)?
+                    };
+                    r.solve(wanted)
+                }
+            }
+        }
+    }
+}
+
+fn read_job<'a>(line: &SourceLine<'a>, text: &'a str) -> Result<Job<'a>, ParseError> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if let [number] = words[..] {
+        return Ok(Job::Yell(line.number(number)?));
+    }
+    let left = line.word(&words, 0, "a monkey name")?;
+    let symbol = line.word(&words, 1, "an operator")?;
+    let right = line.word(&words, 2, "a monkey name")?;
+    let op = match symbol {
+        "+" => Op::Add,
+        "-" => Op::Subtract,
+        "*" => Op::Multiply,
+        "/" => Op::Divide,
+        s => return Err(line.error(s, format!("unrecognised operator `{s}`")))
+    };
+    Ok(Job::Wait(left, op, right))
+}
+
+fn build_tree<'a>(
+    jobs: &HashMap<&'a str, (SourceLine<'a>, Job<'a>)>,
+    name: &'a str,
+    waiting: &mut Vec<&'a str>
+) -> Result<Expr, ParseError> {
+    let (line, job) = &jobs[name];
+    match job {
+        Job::Yell(n) if name == HUMAN => Ok(Expr::Human(*n)),
+        Job::Yell(n) => Ok(Expr::Number(*n)),
+        Job::Wait(l, op, r) => {
+            waiting.push(name);
+            let mut branch = |other: &'a str| {
+                if !jobs.contains_key(other) {
+                    return Err(line.error(other, format!("there is no monkey `{other}`")));
+                }
+                if waiting.contains(&other) {
+                    return Err(line.error(other, format!("`{other}` is waiting on `{name}`'s answer")));
+                }
+                build_tree(jobs, other, waiting)
+            };
+            let expr = Expr::Operation(Box::new(branch(l)?), *op, Box::new(branch(r)?));
+            waiting.pop();
+            Ok(expr)
+        }
+    }
+}
+
+pub fn parse_monkeys(input: &str) -> Result<Expr, ParseError> {
+    let mut jobs: HashMap<&str, (SourceLine, Job)> = HashMap::new();
+    for line in lines(DAY, input) {
+        let (name, rest) = line.text.trim().split_once(':')
+            .ok_or_else(|| line.error(line.text, "expected a line like `root: pppw + sjmn`"))?;
+        let job = read_job(&line, rest)?;
+        jobs.insert(name, (line, job));
+    }
+    if !jobs.contains_key(ROOT) {
+        return Err(ParseError::input(DAY, "there is no `root` monkey"));
+    }
+    build_tree(&jobs, ROOT, &mut Vec::new())
+}
+
+// Root checks its two numbers are equal, so the human's branch has to match the other one
+fn human_value(root: &Expr) -> Option<i64> {
+    let Expr::Operation(l, _, r) = root else { return None };
+    match (l.constant(), r.constant()) {
+        (Some(left), None) => r.solve(left),
+        (None, Some(right)) => l.solve(right),
+        _ => None
+    }
+}
+
+pub fn root_yell(input: &str) -> Result<Option<i64>, ParseError> {
+    Ok(parse_monkeys(input)?.evaluate())
+}
+
+pub fn human_yell(input: &str) -> Result<Option<i64>, ParseError> {
+    Ok(human_value(&parse_monkeys(input)?))
+}
+
+pub struct MonkeyMath;
+
+impl Solver for MonkeyMath {
+    type Parsed = Expr;
+
+    fn parse(&self, input: &str) -> Result<Expr, ParseError> {
+        parse_monkeys(input)
+    }
+
+    fn part1(&self, root: &Expr) -> Answer {
+        root.evaluate().into()
+    }
+
+    fn part2(&self, root: &Expr) -> Answer {
+        human_value(root).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, MonkeyMath, 152, 301)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_one() {
+        assert_eq!(root_yell(EXAMPLE), Ok(Some(152)));
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(human_yell(EXAMPLE), Ok(Some(301)));
+    }
+
+    #[test]
+    fn human_on_the_right() {
+        // 10 - (humn / 2) has to equal 4
+        let input = "root: ten + hmm\nten: 4\nhmm: x - half\nx: 10\nhalf: humn / two\nhumn: 0\ntwo: 2";
+        assert_eq!(human_yell(input), Ok(Some(12)));
+    }
+
+    #[test]
+    fn waiting_in_a_circle() {
+        let error = parse_monkeys("root: aaaa + bbbb\naaaa: 3\nbbbb: cccc * aaaa\ncccc: bbbb - aaaa").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 7));
+    }
+}
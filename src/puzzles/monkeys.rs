@@ -1,32 +1,66 @@
 use std::collections::VecDeque;
+use crate::error::ParseError;
+use crate::parsing::{groups, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
 
-#[derive(Debug, PartialEq)]
+const DAY: u8 = 11;
+
+pub const EXAMPLE: &str = r"Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+#[derive(Debug, Clone, PartialEq)]
 enum Operation {
     Plus (u64),
     Multiply (u64),
     Square
 }
 
-fn build_operation(input: Vec<&str>) -> Operation {
-    match input.get(1).unwrap() {
-        &"+" => {
-            let value: u64 = input.get(0).unwrap().parse().unwrap();
-            Operation::Plus(value)
+fn build_operation(line: &SourceLine, input: Vec<&str>) -> Result<Operation, ParseError> {
+    let value = line.word(&input, 0, "an operand")?;
+    match line.word(&input, 1, "an operator")? {
+        "+" => {
+            let value: u64 = line.number(value)?;
+            Ok(Operation::Plus(value))
         },
-        &"*" => {
-            let value = input.get(0).unwrap();
-            if value == &"old" {
-                return Operation::Square
+        "*" => {
+            if value == "old" {
+                return Ok(Operation::Square)
             }
-            let value: u64 = value.parse().unwrap();
-            Operation::Multiply(value)
+            let value: u64 = line.number(value)?;
+            Ok(Operation::Multiply(value))
         }
-        n => panic!("Unrecognised build op {n}")
+        n => Err(line.error(n, format!("unrecognised operator `{n}`")))
     }
 }
 
-#[derive(Debug)]
-struct Monkey {
+#[derive(Debug, Clone)]
+pub struct Monkey {
     items: VecDeque<u64>,
     operation: Operation,
     div_check: u64,
@@ -48,13 +82,13 @@ impl Monkey {
 
         if !self.worried {
             // Decrease worry level because you are chill
-            item = item / 3;
+            item /= 3;
         } else {
             // I'm pretty sure this is how clock maths works?
-            item = item % max_worry
+            item %= max_worry
         }
 
-        let index = if item % self.div_check == 0 {
+        let index = if item.is_multiple_of(self.div_check) {
             self.truth_index
         } else {
             self.false_index
@@ -67,44 +101,68 @@ impl Monkey {
     }
 }
 
-fn parse_monkey(input: &[&str], worried: bool) -> Monkey {
-    // First line is number, skip
-    let mut iter = input.iter().skip(1);
-
-    let items: VecDeque<u64> = iter.next().unwrap().trim().chars()
-        .filter(|c| c.is_numeric() || c.is_whitespace()).collect::<String>()
-        .trim().split(" ").map(|v| v.parse().unwrap()).collect();
+fn last_number(line: &SourceLine) -> Result<u64, ParseError> {
+    let words: Vec<&str> = line.text.split_whitespace().collect();
+    line.number(words[words.len()-1])
+}
 
-    let op_string: Vec<&str> = iter.next().unwrap().trim().split(" ").collect();
-    let relevant_parts: Vec<&str> = op_string.iter().rev().take(2).map(|s| *s).collect();
-    let operation = build_operation(relevant_parts);
+fn parse_monkey(input: &[SourceLine], monkey_count: usize) -> Result<Monkey, ParseError> {
+    // First line is number, skip
+    let header = input[0];
+    let get_line = |index: usize, what: &str| input.get(index).ok_or_else(|| {
+        header.error(&header.text[header.text.len()..], format!("monkey is missing its {what}"))
+    });
+
+    let items_line = get_line(1, "starting items")?;
+    let (_, item_list) = items_line.text.split_once(':')
+        .ok_or_else(|| items_line.error(items_line.text.trim(), "expected `Starting items: ...`"))?;
+    let items: VecDeque<u64> = item_list.split(',').map(|v| v.trim()).filter(|v| !v.is_empty())
+        .map(|v| items_line.number(v)).collect::<Result<_, _>>()?;
+
+    let op_line = get_line(2, "operation")?;
+    let op_string: Vec<&str> = op_line.text.split_whitespace().collect();
+    let relevant_parts: Vec<&str> = op_string.iter().rev().take(2).copied().collect();
+    let operation = build_operation(op_line, relevant_parts)?;
+
+    let div_line = get_line(3, "divisibility test")?;
+    let divisibility = last_number(div_line)?;
+    if divisibility == 0 {
+        return Err(div_line.error(div_line.text.trim_end(), "cannot test divisibility by zero"));
+    }
 
-    let divisibility: u64 = iter.next().unwrap().trim().split(" ").last().unwrap().parse().unwrap();
-    let truth_index: u64 = iter.next().unwrap().trim().split(" ").last().unwrap().parse().unwrap();
-    let false_index: u64 = iter.next().unwrap().trim().split(" ").last().unwrap().parse().unwrap();
+    let target = |index: usize, what: &str| -> Result<u64, ParseError> {
+        let line = get_line(index, what)?;
+        let monkey = last_number(line)?;
+        if monkey as usize >= monkey_count {
+            return Err(line.error(line.text.trim_end(), format!("there is no monkey {monkey}")));
+        }
+        Ok(monkey)
+    };
+    let truth_index = target(4, "true target")?;
+    let false_index = target(5, "false target")?;
 
-    Monkey {
+    Ok(Monkey {
         items,
         operation,
         div_check: divisibility,
         truth_index,
         false_index,
-        worried
-    }
+        worried: false
+    })
 }
 
-fn parse_monkeys(input: &String, worried: bool) -> Vec<Monkey> {
-    let vectored: Vec<&str> = input.trim().lines().collect();
-    let grouped: Vec<&[&str]> = vectored.split(|s| *s == "").collect();
+pub fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let grouped = groups(DAY, input);
 
-    grouped.iter().map(|m| {
-        let ms = *m;
-        parse_monkey(ms, worried)
-    }).collect()
+    let monkeys = grouped.iter().map(|m| parse_monkey(m, grouped.len())).collect::<Result<Vec<_>, _>>()?;
+    if monkeys.len() < 2 {
+        return Err(ParseError::input(DAY, "monkey business needs at least two monkeys"));
+    }
+    Ok(monkeys)
 }
 
-pub fn monkey_business(input: &String, worried: bool) -> u64 {
-    let mut monkeys = parse_monkeys(input, worried);
+fn watch_monkeys(mut monkeys: Vec<Monkey>, worried: bool) -> u64 {
+    monkeys.iter_mut().for_each(|m| m.worried = worried);
     let mut throw_count: Vec<u64> = (0..monkeys.len()).map(|_| 0).collect();
 
     let max_worry_level: u64 = monkeys.iter().map(|m| m.div_check).product();
@@ -112,14 +170,14 @@ pub fn monkey_business(input: &String, worried: bool) -> u64 {
     let max_rounds = if worried { 10000 } else { 20 };
 
     for _ in 0..max_rounds {
-        for i in 0..monkeys.len() {
+        for (i, count) in throw_count.iter_mut().enumerate() {
             let mut thrown_items: VecDeque<(u64, u64)> = VecDeque::new();
             let monkey = monkeys.get_mut(i).unwrap();
-            throw_count[i] += monkey.items.len() as u64;
-            while monkey.items.len() > 0 {
+            *count += monkey.items.len() as u64;
+            while !monkey.items.is_empty() {
                 thrown_items.push_back(monkey.throw(max_worry_level));
             }
-            while thrown_items.len() > 0 {
+            while !thrown_items.is_empty() {
                 let (item, index) = thrown_items.pop_front().unwrap();
                 monkeys.get_mut(index as usize).unwrap().catch(item);
             }
@@ -129,44 +187,62 @@ pub fn monkey_business(input: &String, worried: bool) -> u64 {
     throw_count.iter().rev().take(2).product()
 }
 
+pub fn monkey_business(input: &str, worried: bool) -> Result<u64, ParseError> {
+    Ok(watch_monkeys(parse_monkeys(input)?, worried))
+}
+
+pub struct MonkeyInTheMiddle;
+
+impl Solver for MonkeyInTheMiddle {
+    type Parsed = Vec<Monkey>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Monkey>, ParseError> {
+        parse_monkeys(input)
+    }
+
+    fn part1(&self, monkeys: &Vec<Monkey>) -> Answer {
+        watch_monkeys(monkeys.clone(), false).into()
+    }
+
+    fn part2(&self, monkeys: &Vec<Monkey>) -> Answer {
+        watch_monkeys(monkeys.clone(), true).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, MonkeyInTheMiddle, 10605, 2713310158u64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn example() {
+        assert_eq!(monkey_business(EXAMPLE, false), Ok(10605));
+
+        assert_eq!(monkey_business(EXAMPLE, true), Ok(2713310158))
+    }
+
+    #[test]
+    fn missing_monkey() {
         let input = r"
 Monkey 0:
 Starting items: 79, 98
 Operation: new = old * 19
 Test: divisible by 23
     If true: throw to monkey 2
-    If false: throw to monkey 3
+    If false: throw to monkey 1
 
 Monkey 1:
-Starting items: 54, 65, 75, 74
+Starting items: 54
 Operation: new = old + 6
 Test: divisible by 19
-    If true: throw to monkey 2
-    If false: throw to monkey 0
-
-Monkey 2:
-Starting items: 79, 60, 97
-Operation: new = old * old
-Test: divisible by 13
-    If true: throw to monkey 1
-    If false: throw to monkey 3
-
-Monkey 3:
-Starting items: 74
-Operation: new = old + 3
-Test: divisible by 17
     If true: throw to monkey 0
-    If false: throw to monkey 1
-    ".to_string();
-
-        assert_eq!(monkey_business(&input, false), 10605);
+    If false: throw to monkey 0";
 
-        assert_eq!(monkey_business(&input, true), 2713310158)
+        let error = parse_monkeys(input).unwrap_err();
+        assert_eq!(error.line, 6);
+        assert_eq!(error.reason, "there is no monkey 2");
     }
 }
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use crate::error::ParseError;
+use crate::parsing::lines;
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 17;
+
+pub const EXAMPLE: &str = r">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+const WIDTH: i32 = 7;
+// How many rows from the top of the tower count as its surface when looking for a cycle
+const SURFACE: usize = 32;
+
+// Each rock is a list of rows from the bottom up, with bit i set for a block in column i
+const ROCKS: [&[u16]; 5] = [
+    &[0b1111],
+    &[0b010, 0b111, 0b010],
+    &[0b111, 0b100, 0b100],
+    &[0b1, 0b1, 0b1, 0b1],
+    &[0b11, 0b11],
+];
+
+// -1 pushes left, 1 pushes right
+pub type Jets = Vec<i32>;
+
+pub fn read_jets(input: &str) -> Result<Jets, ParseError> {
+    let line = lines(DAY, input).next().ok_or_else(|| ParseError::input(DAY, "there is no jet pattern"))?;
+    let text = line.text.trim();
+    text.char_indices().map(|(i, c)| match c {
+        '<' => Ok(-1),
+        '>' => Ok(1),
+        _ => Err(line.error(&text[i..], format!("`{c}` is not a jet, expected `<` or `>`")))
+    }).collect()
+}
+
+struct Chamber<'a> {
+    jets: &'a Jets,
+    rows: Vec<u16>,
+    jet: usize,
+    rock: usize
+}
+
+impl<'a> Chamber<'a> {
+    fn new(jets: &'a Jets) -> Self {
+        Chamber { jets, rows: Vec::new(), jet: 0, rock: 0 }
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn fits(&self, shape: &[u16], x: i32, y: usize) -> bool {
+        if x < 0 {
+            return false;
+        }
+        shape.iter().enumerate().all(|(i, row)| {
+            let placed = row << x;
+            placed < 1 << WIDTH && self.rows.get(y + i).is_none_or(|r| r & placed == 0)
+        })
+    }
+
+    fn drop_rock(&mut self) {
+        let shape = ROCKS[self.rock];
+        self.rock = (self.rock + 1) % ROCKS.len();
+        let mut x: i32 = 2;
+        let mut y: usize = self.height() + 3;
+
+        loop {
+            let pushed = x + self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
+            if self.fits(shape, pushed, y) {
+                x = pushed;
+            }
+            if y == 0 || !self.fits(shape, x, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+
+        for (i, row) in shape.iter().enumerate() {
+            if y + i == self.rows.len() {
+                self.rows.push(0);
+            }
+            self.rows[y + i] |= row << x;
+        }
+    }
+
+    // Everything that decides how the next rocks will fall
+    fn state(&self) -> (usize, usize, Vec<u16>) {
+        let top = self.rows.iter().rev().take(SURFACE).copied().collect();
+        (self.rock, self.jet, top)
+    }
+}
+
+pub fn tower_height(jets: &Jets, rocks: u64) -> u64 {
+    let mut chamber = Chamber::new(jets);
+    let mut seen: HashMap<(usize, usize, Vec<u16>), (u64, usize)> = HashMap::new();
+    let mut skipped: u64 = 0;
+    let mut dropped: u64 = 0;
+
+    while dropped < rocks {
+        chamber.drop_rock();
+        dropped += 1;
+
+        // Once the surface repeats, jump ahead by as many whole cycles as fit
+        if skipped == 0 && chamber.height() >= SURFACE {
+            if let Some((before, height)) = seen.insert(chamber.state(), (dropped, chamber.height())) {
+                let period = dropped - before;
+                let cycles = (rocks - dropped) / period;
+                dropped += cycles * period;
+                skipped = cycles * (chamber.height() - height) as u64;
+            }
+        }
+    }
+
+    chamber.height() as u64 + skipped
+}
+
+pub fn height_after_2022(input: &str) -> Result<u64, ParseError> {
+    Ok(tower_height(&read_jets(input)?, 2022))
+}
+
+pub fn height_after_trillion(input: &str) -> Result<u64, ParseError> {
+    Ok(tower_height(&read_jets(input)?, 1_000_000_000_000))
+}
+
+pub struct PyroclasticFlow;
+
+impl Solver for PyroclasticFlow {
+    type Parsed = Jets;
+
+    fn parse(&self, input: &str) -> Result<Jets, ParseError> {
+        read_jets(input)
+    }
+
+    fn part1(&self, jets: &Jets) -> Answer {
+        tower_height(jets, 2022).into()
+    }
+
+    fn part2(&self, jets: &Jets) -> Answer {
+        tower_height(jets, 1_000_000_000_000).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, PyroclasticFlow, 3068, 1514285714288u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_rocks() {
+        let jets = read_jets(EXAMPLE).unwrap();
+        assert_eq!(tower_height(&jets, 1), 1);
+        assert_eq!(tower_height(&jets, 2), 4);
+        assert_eq!(tower_height(&jets, 10), 17);
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(height_after_2022(EXAMPLE), Ok(3068));
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(height_after_trillion(EXAMPLE), Ok(1514285714288));
+    }
+
+    #[test]
+    fn bad_jet() {
+        let error = read_jets(">><x>").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+    }
+}
@@ -1,55 +1,237 @@
-use std::collections::HashSet;
+use std::fmt;
+use crate::error::ParseError;
+use crate::parsing::{lines, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
 
-fn pack_input(input: &String) -> (String, String) {
-    let midpoint = input.len() / 2;
-    let (lhs, rhs) = input.split_at(midpoint);
-    (lhs.to_string(), rhs.to_string())
+const DAY: u8 = 3;
+
+pub const EXAMPLE: &str = r"vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+pub type Rucksacks = Vec<String>;
+
+pub fn read_rucksacks(input: &str) -> Result<Rucksacks, ParseError> {
+    lines(DAY, input).map(|line| {
+        let items = line.text.trim();
+        if let Some((i, c)) = items.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            return Err(line.error(&items[i..], format!("`{c}` is not an item, expected a letter")));
+        }
+        Ok(items.to_string())
+    }).collect()
 }
 
-fn find_shared(left: &String, right: &String) -> Vec<u8>{
-    let left_set: HashSet<u8> = HashSet::from_iter(left.bytes());
-    let right_set: HashSet<u8> = HashSet::from_iter(right.bytes());
+pub const COMPARTMENTS: usize = 2;
+pub const GROUP_SIZE: usize = 3;
+
+// A set of items as one bit per priority, from bit 1 for `a` to bit 52 for `Z`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemSet(u64);
 
-    left_set.intersection(&right_set).map(|c| *c).collect()
+pub fn priority(item: u8) -> Option<u32> {
+    match item {
+        b'a'..=b'z' => Some((item - b'a') as u32 + 1),
+        b'A'..=b'Z' => Some((item - b'A') as u32 + 27),
+        _ => None
+    }
 }
 
-fn get_value(c: u8) -> u32 {
-    if c <= b'Z' {
-        ((c - b'A') +27) as u32
-    } else {
-        ((c - b'a') + 1) as u32
+fn item(priority: u32) -> u8 {
+    match priority {
+        1..=26 => b'a' + (priority - 1) as u8,
+        _ => b'A' + (priority - 27) as u8
     }
 }
 
-pub fn value_shared_priorities(input: &String) -> u32{
-    input.trim().lines().fold(0, |acc, line| {
-        let (lhs, rhs) = pack_input(&line.to_string());
-        let shared = find_shared(&lhs, &rhs);
-        acc + shared.iter().fold(0, |acc, byte| get_value(*byte) + acc)
-    })
+impl ItemSet {
+    pub const ALL: ItemSet = ItemSet(((1 << 52) - 1) << 1);
+
+    // Anything that isn't a letter is left out
+    pub fn from_items(items: impl AsRef<[u8]>) -> Self {
+        ItemSet(items.as_ref().iter().filter_map(|i| priority(*i)).fold(0, |set, p| set | 1 << p))
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn contains(&self, item: u8) -> bool {
+        priority(item).is_some_and(|p| self.0 & 1 << p != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = u8> + '_ {
+        (1..=52).filter(|p| self.0 & 1 << p != 0).map(item)
+    }
+
+    pub fn priorities(&self) -> u32 {
+        (1..=52).filter(|p| self.0 & 1 << p != 0).sum()
+    }
+}
+
+impl fmt::Display for ItemSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", String::from_utf8(self.items().collect()).expect("Items are letters"))
+    }
+}
+
+// Splits into `count` equal parts, with anything left over going in the last
+pub fn compartments(rucksack: &str, count: usize) -> Vec<&[u8]> {
+    let rucksack = rucksack.as_bytes();
+    let size = rucksack.len() / count.max(1);
+    let mut parts: Vec<&[u8]> = (0..count.saturating_sub(1)).map(|i| &rucksack[i * size..(i + 1) * size]).collect();
+    parts.push(&rucksack[size * parts.len()..]);
+    parts
+}
+
+fn common(sets: impl Iterator<Item = ItemSet>) -> ItemSet {
+    sets.fold(ItemSet::ALL, ItemSet::intersection)
+}
+
+// What each rucksack's compartments have in common
+pub fn shared_items(rucksacks: &Rucksacks, count: usize) -> Vec<ItemSet> {
+    rucksacks.iter()
+        .map(|r| common(compartments(r, count).into_iter().map(ItemSet::from_items)))
+        .collect()
+}
+
+// What each whole group of `size` rucksacks has in common
+pub fn group_items(rucksacks: &Rucksacks, size: usize) -> Vec<ItemSet> {
+    rucksacks.chunks_exact(size.max(1))
+        .map(|group| common(group.iter().map(ItemSet::from_items)))
+        .collect()
 }
 
-pub fn badge_groups(input: &String) -> u32{
-    let mut lines = input.trim().lines().peekable();
+fn shared_priorities(rucksacks: &Rucksacks) -> u32 {
+    shared_items(rucksacks, COMPARTMENTS).iter().map(|s| s.priorities()).sum()
+}
+
+pub fn value_shared_priorities(input: &str) -> Result<u32, ParseError> {
+    Ok(shared_priorities(&read_rucksacks(input)?))
+}
 
-    let mut final_value = 0;
+// Every group has to have exactly one badge between them
+fn group_badges(rucksacks: &Rucksacks) -> Option<u32> {
+    group_items(rucksacks, GROUP_SIZE).iter()
+        .map(|badge| (badge.len() == 1).then(|| badge.priorities()))
+        .sum()
+}
+
+pub fn badge_groups(input: &str) -> Result<Option<u32>, ParseError> {
+    Ok(group_badges(&read_rucksacks(input)?))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    UnevenLength { length: usize, compartments: usize },
+    NotAnItem { column: usize, found: char },
+    SharedItems(ItemSet),
+    // Reported against the group's first rucksack
+    Badges(ItemSet),
+    PartialGroup { rucksacks: usize, size: usize }
+}
 
-    while lines.peek().is_some() { 
-        let initial = lines.next().expect("Should be a line here");
-        let mut group_shared: HashSet<u8> = HashSet::from_iter(initial.bytes());
-        for _ in 1..3 {
-            let current = lines.next().expect("Line must exist at this point");
-            group_shared = group_shared.intersection(&HashSet::from_iter(current.bytes())).map(|c| *c).collect();
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub line: usize,
+    pub problem: Problem
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let items = |set: &ItemSet| match set.is_empty() {
+            true => "nothing".to_string(),
+            false => set.items().map(|i| format!("`{}`", i as char)).collect::<Vec<String>>().join(", ")
+        };
+        write!(f, "line {}: ", self.line)?;
+        match &self.problem {
+            Problem::UnevenLength { length, compartments } =>
+                write!(f, "{length} items don't split evenly into {compartments} compartments"),
+            Problem::NotAnItem { column, found } =>
+                write!(f, "`{found}` in column {column} is not an item, expected a letter"),
+            Problem::SharedItems(set) =>
+                write!(f, "the compartments share {}, expected exactly one item", items(set)),
+            Problem::Badges(set) =>
+                write!(f, "the group shares {}, expected exactly one badge", items(set)),
+            Problem::PartialGroup { rucksacks, size } =>
+                write!(f, "the last group only has {rucksacks} of {size} rucksacks")
         }
+    }
+}
+
+// Checks the raw input for everything the puzzle takes for granted, rather than stopping
+// at the first problem the way `read_rucksacks` does
+pub fn validate(input: &str, compartment_count: usize, group_size: usize) -> Vec<Finding> {
+    let rucksacks: Vec<SourceLine> = lines(DAY, input).collect();
+    let mut findings: Vec<Finding> = Vec::new();
+    let mut found = |line: &SourceLine, problem| findings.push(Finding { line: line.number, problem });
 
-        if group_shared.len() != 1 {
-            panic!("Should only be one element left in {group_shared:?}");
+    for line in &rucksacks {
+        let items = line.text.trim();
+        let length = items.chars().count();
+        if length % compartment_count.max(1) != 0 {
+            found(line, Problem::UnevenLength { length, compartments: compartment_count });
+        }
+        if let Some((column, found_char)) = items.chars().enumerate().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            found(line, Problem::NotAnItem { column: column + 1, found: found_char });
+        }
+        let shared = common(compartments(items, compartment_count).into_iter().map(ItemSet::from_items));
+        if shared.len() != 1 {
+            found(line, Problem::SharedItems(shared));
+        }
+    }
+
+    let size = group_size.max(1);
+    for group in rucksacks.chunks(size) {
+        if group.len() < size {
+            found(&group[0], Problem::PartialGroup { rucksacks: group.len(), size });
+            continue;
+        }
+        let badges = common(group.iter().map(|l| ItemSet::from_items(l.text.trim())));
+        if badges.len() != 1 {
+            found(&group[0], Problem::Badges(badges));
         }
+    }
+
+    findings.sort_by_key(|f| f.line);
+    findings
+}
 
-        final_value = final_value + group_shared.iter().fold(0, |acc, x| get_value(*x) + acc);
+pub struct RucksackReorganization;
+
+impl Solver for RucksackReorganization {
+    type Parsed = Rucksacks;
+
+    fn parse(&self, input: &str) -> Result<Rucksacks, ParseError> {
+        read_rucksacks(input)
     }
 
-    final_value
+    fn part1(&self, rucksacks: &Rucksacks) -> Answer {
+        shared_priorities(rucksacks).into()
+    }
+
+    fn part2(&self, rucksacks: &Rucksacks) -> Answer {
+        group_badges(rucksacks).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, RucksackReorganization, 157, 70)
 }
 
 #[cfg(test)]
@@ -58,29 +240,62 @@ mod tests {
 
     #[test]
     fn part_one_example() {
-        let input = r"
-vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw
-        ".to_string();
-
-        assert_eq!(value_shared_priorities(&input), 157);
+        assert_eq!(value_shared_priorities(EXAMPLE), Ok(157));
     }
 
     #[test]
     fn part_two_example() {
-        let input = r"
-vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw
-        ".to_string();
+        assert_eq!(badge_groups(EXAMPLE), Ok(Some(70)));
+    }
+
+    #[test]
+    fn no_single_badge() {
+        assert_eq!(badge_groups("abAB\ncdCD\nefEF"), Ok(None));
+    }
+
+    #[test]
+    fn validating() {
+        assert_eq!(validate(EXAMPLE, COMPARTMENTS, GROUP_SIZE), vec![]);
+
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\nabcab\nabc1bc\n\nxyzxyz";
+        let findings = validate(input, COMPARTMENTS, GROUP_SIZE);
+        assert_eq!(findings, vec![
+            Finding { line: 2, problem: Problem::UnevenLength { length: 5, compartments: 2 } },
+            Finding { line: 2, problem: Problem::SharedItems(ItemSet::from_items("ab")) },
+            Finding { line: 3, problem: Problem::NotAnItem { column: 4, found: '1' } },
+            Finding { line: 3, problem: Problem::SharedItems(ItemSet::from_items("bc")) },
+            Finding { line: 5, problem: Problem::SharedItems(ItemSet::from_items("xyz")) },
+            Finding { line: 5, problem: Problem::PartialGroup { rucksacks: 1, size: 3 } }
+        ]);
+        assert_eq!(findings[3].to_string(), "line 3: the compartments share `b`, `c`, expected exactly one item");
+
+        let badges = validate("abAB\ncdCD\nefEF", COMPARTMENTS, GROUP_SIZE);
+        assert!(badges.contains(&Finding { line: 1, problem: Problem::Badges(ItemSet::default()) }));
+    }
+
+    #[test]
+    fn item_sets() {
+        let left = ItemSet::from_items("vJrwpWtwJgWr");
+        let right = ItemSet::from_items("hcsFMMfFFhFp");
+        assert_eq!(left.intersection(right).to_string(), "p");
+        assert_eq!(left.union(right).len(), 14);
+        assert!(left.contains(b'J') && !left.contains(b'j'));
+        assert_eq!(ItemSet::from_items("aZ").priorities(), 1 + 52);
+        assert_eq!(ItemSet::ALL.len(), 52);
+    }
+
+    #[test]
+    fn shared_and_badges() {
+        let rucksacks = read_rucksacks(EXAMPLE).unwrap();
+        let shared: Vec<String> = shared_items(&rucksacks, COMPARTMENTS).iter().map(|s| s.to_string()).collect();
+        assert_eq!(shared, ["p", "L", "P", "v", "t", "s"]);
+        let badges: Vec<String> = group_items(&rucksacks, GROUP_SIZE).iter().map(|s| s.to_string()).collect();
+        assert_eq!(badges, ["r", "Z"]);
 
-        assert_eq!(badge_groups(&input), 70);
+        // Two groups of two, and thirds of each rucksack
+        assert_eq!(group_items(&rucksacks, 2).len(), 3);
+        assert_eq!(compartments("abcdefg", 3), [&b"ab"[..], b"cd", b"efg"]);
+        let rucksacks = vec!["abbcbd".to_string()];
+        assert_eq!(shared_items(&rucksacks, 3)[0].to_string(), "b");
     }
 }
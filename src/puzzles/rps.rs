@@ -1,78 +1,287 @@
-type Guide = Vec<(char, char)>;
-
-fn read_guide(input: &String) -> Guide{
-    input.trim().lines().map(|l| {
-        let mut chars = l.chars();
-        let lhs = chars.next().expect("Should be a first character");
-        chars.next();
-        let rhs = chars.next().expect("Should be a third character");
-        (lhs, rhs)
+use std::cmp::Reverse;
+use crate::error::ParseError;
+use crate::parsing::lines;
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
+
+pub mod tournament;
+
+const DAY: u8 = 2;
+
+pub const EXAMPLE: &str = r"A Y
+B X
+C Z";
+
+pub type Guide = Vec<(char, char)>;
+
+pub fn read_guide(input: &str) -> Result<Guide, ParseError> {
+    lines(DAY, input).map(|line| {
+        let words: Vec<&str> = line.text.split_whitespace().collect();
+        let lhs = line.word(&words, 0, "the opponent's hand")?;
+        let rhs = line.word(&words, 1, "a response")?;
+        if !matches!(lhs, "A" | "B" | "C") {
+            return Err(line.error(lhs, format!("unknown opponent hand `{lhs}`, expected A, B or C")));
+        }
+        if !matches!(rhs, "X" | "Y" | "Z") {
+            return Err(line.error(rhs, format!("unknown response `{rhs}`, expected X, Y or Z")));
+        }
+        if let Some(extra) = words.get(2) {
+            return Err(line.error(extra, "unexpected text after the response"));
+        }
+        Ok((lhs.chars().next().unwrap(), rhs.chars().next().unwrap()))
+    }).collect()
+}
+
+pub type Hand = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win
+}
+
+// What a symbol in the second column stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Response {
+    Hand(Hand),
+    Outcome(Outcome)
+}
+
+// A game: its hands, which hands beat which, and the points for playing each hand and for
+// each outcome. Hands that neither beat the other draw.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rules {
+    names: Vec<String>,
+    points: Vec<u32>,
+    beats: Vec<Vec<bool>>,
+    // Lose, draw, win
+    outcome_points: [u32; 3]
+}
+
+impl Rules {
+    pub fn new(hands: &[(&str, u32)], beats: &[(&str, &str)], outcome_points: [u32; 3]) -> Result<Self, String> {
+        let names: Vec<String> = hands.iter().map(|(name, _)| name.to_string()).collect();
+        if let Some(i) = (1..names.len()).find(|i| names[..*i].contains(&names[*i])) {
+            return Err(format!("`{}` is in the game twice", names[i]));
+        }
+        let mut rules = Rules {
+            points: hands.iter().map(|(_, points)| *points).collect(),
+            beats: vec![vec![false; names.len()]; names.len()],
+            names,
+            outcome_points
+        };
+
+        for (winner, loser) in beats {
+            let hand = |name: &str| rules.hand(name).ok_or(format!("`{name}` isn't one of the hands"));
+            let (w, l) = (hand(winner)?, hand(loser)?);
+            if w == l {
+                return Err(format!("`{winner}` can't beat itself"));
+            }
+            if rules.beats[l][w] {
+                return Err(format!("`{winner}` and `{loser}` both beat each other"));
+            }
+            rules.beats[w][l] = true;
+        }
+        Ok(rules)
+    }
+
+    pub fn rock_paper_scissors() -> Self {
+        let hands = [("Rock", 1), ("Paper", 2), ("Scissors", 3)];
+        let beats = [("Rock", "Scissors"), ("Paper", "Rock"), ("Scissors", "Paper")];
+        Rules::new(&hands, &beats, [0, 3, 6]).expect("The standard game is valid")
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        let hands = [("Rock", 1), ("Paper", 2), ("Scissors", 3), ("Lizard", 4), ("Spock", 5)];
+        let beats = [
+            ("Scissors", "Paper"), ("Paper", "Rock"), ("Rock", "Lizard"), ("Lizard", "Spock"), ("Spock", "Scissors"),
+            ("Scissors", "Lizard"), ("Lizard", "Paper"), ("Paper", "Spock"), ("Spock", "Rock"), ("Rock", "Scissors")
+        ];
+        Rules::new(&hands, &beats, [0, 3, 6]).expect("The standard game is valid")
+    }
+
+    pub fn hands(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, hand: Hand) -> &str {
+        &self.names[hand]
+    }
+
+    pub fn hand(&self, name: &str) -> Option<Hand> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    pub fn outcome(&self, you: Hand, them: Hand) -> Outcome {
+        match (self.beats[you][them], self.beats[them][you]) {
+            (true, _) => Outcome::Win,
+            (_, true) => Outcome::Lose,
+            _ => Outcome::Draw
+        }
+    }
+
+    pub fn score(&self, you: Hand, them: Hand) -> u32 {
+        self.points[you] + self.outcome_points[self.outcome(you, them) as usize]
+    }
+
+    // The hand that gets `outcome` against `them`. If several do, the one scoring the most,
+    // and the first of those.
+    pub fn response(&self, them: Hand, outcome: Outcome) -> Option<Hand> {
+        (0..self.hands())
+            .filter(|you| self.outcome(*you, them) == outcome)
+            .min_by_key(|you| Reverse(self.points[*you]))
+    }
+
+    pub fn play(&self, them: Hand, response: Response) -> Option<Hand> {
+        match response {
+            Response::Hand(hand) => (hand < self.hands()).then_some(hand),
+            Response::Outcome(outcome) => self.response(them, outcome)
+        }
+    }
+}
+
+// What the guide's symbols stand for: the first column's A, B, C... are the opponent's
+// hands, and the second column's X, Y, Z... are responses
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    pub opponent: Vec<Hand>,
+    pub response: Vec<Response>
+}
+
+impl Reading {
+    // The second column is your hand, in the same order as the first
+    pub fn hands(rules: &Rules) -> Self {
+        let hands: Vec<Hand> = (0..rules.hands()).collect();
+        Reading { response: hands.iter().map(|h| Response::Hand(*h)).collect(), opponent: hands }
+    }
+
+    // The second column is how the round should end
+    pub fn outcomes(rules: &Rules) -> Self {
+        let response = [Outcome::Lose, Outcome::Draw, Outcome::Win].map(Response::Outcome).to_vec();
+        Reading { opponent: (0..rules.hands()).collect(), response }
+    }
+
+    // The opponent's hand and yours
+    pub fn decode(&self, rules: &Rules, (them, you): (char, char)) -> Option<(Hand, Hand)> {
+        let symbol = |c: char, first: char| (c as usize).checked_sub(first as usize);
+        let them = *self.opponent.get(symbol(them, 'A')?)?;
+        if them >= rules.hands() {
+            return None;
+        }
+        let response = *self.response.get(symbol(you, 'X')?)?;
+        Some((them, rules.play(them, response)?))
+    }
+
+    // Each symbol and what it stands for, like `A=Rock ... X=Lose`
+    pub fn describe(&self, rules: &Rules) -> String {
+        let opponent = self.opponent.iter().zip('A'..).map(|(h, c)| format!("{c}={}", rules.name(*h)));
+        let response = self.response.iter().zip('X'..).map(|(r, c)| match r {
+            Response::Hand(h) => format!("{c}={}", rules.name(*h)),
+            Response::Outcome(o) => format!("{c}={o:?}")
+        });
+        opponent.chain(response).collect::<Vec<String>>().join(" ")
+    }
+}
+
+// A way of reading the guide and the score it gives
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoding {
+    pub reading: Reading,
+    pub score: u32
+}
+
+fn permutations<T: Copy>(items: &[T]) -> Vec<Vec<T>> {
+    if items.is_empty() {
+        return vec![Vec::new()];
+    }
+    (0..items.len()).flat_map(|i| {
+        let mut rest = items.to_vec();
+        let first = rest.remove(i);
+        permutations(&rest).into_iter().map(move |mut p| {
+            p.insert(0, first);
+            p
+        })
     }).collect()
 }
 
-fn turn_score(game: (char, char)) -> u32 {
-    // A: Rock B: Paper C: Scissors
-    // X: Rock Y: Paper Z: Scissors
-    // R: 1 P: 2 S: 3
-    // L: 0 D: 3 W: 6
-    let (_, you) = game;
-    let mut score = 0;
-    score += match you {
-        'X' => 1,
-        'Y' => 2,
-        'Z' => 3,
-        _ => panic!("Unknown hand {you}")
-    };
-
-    score += match game  {
-        ('A', 'X') => 3,
-        ('B', 'Y') => 3,
-        ('C', 'Z') => 3,
-        ('A', 'Z') => 0,
-        ('B', 'X') => 0,
-        ('C', 'Y') => 0,
-        ('A', 'Y') => 6,
-        ('B', 'Z') => 6,
-        ('C', 'X') => 6,
-        _ => panic!("Invalid game {game:?}")
-    };
-
-    score
-}
-
-fn game_score(guide: Guide) -> u32 {
-    guide.iter().map(|g| turn_score(*g)).sum()
-}
-
-pub fn guide_score(input: &String) -> u32 {
-    let g = read_guide(input);
-    game_score(g)
-}
-
-fn turn_conversion(turn: (char, char)) -> (char, char) {
-    // A: Rock B: Paper C: Scissors
-    // in   - X: lose Y: draw Z: win
-    // out  - X: Rock Y: Paper Z: Scissors
-    let (them, _) = turn;
-    let second = match turn {
-        ('A', 'X') => 'Z',
-        ('B', 'X') => 'X',
-        ('C', 'X') => 'Y',
-        ('A', 'Y') => 'X',
-        ('B', 'Y') => 'Y',
-        ('C', 'Y') => 'Z',
-        ('A', 'Z') => 'Y',
-        ('B', 'Z') => 'Z',
-        ('C', 'Z') => 'X',
-        _ => panic!("Unrecognized game {turn:?}")
-    };
-    (them, second)
-}
-
-pub fn true_guide_score(input: &String) -> u32 {
-    let g = read_guide(input);
-    let true_g: Guide = g.iter().map(|t| turn_conversion(*t)).collect();
-    game_score(true_g)
+// Every way of assigning the opponent's symbols to hands, together with every way of
+// assigning the response symbols either to hands or to outcomes
+pub fn readings(rules: &Rules) -> Vec<Reading> {
+    let hands: Vec<Hand> = (0..rules.hands()).collect();
+    let as_hands = permutations(&hands).into_iter()
+        .map(|p| p.into_iter().map(Response::Hand).collect::<Vec<Response>>());
+    let as_outcomes = permutations(&[Outcome::Lose, Outcome::Draw, Outcome::Win]).into_iter()
+        .map(|p| p.into_iter().map(Response::Outcome).collect::<Vec<Response>>());
+    let responses: Vec<Vec<Response>> = as_hands.chain(as_outcomes).collect();
+
+    permutations(&hands).into_iter().flat_map(|opponent| {
+        responses.iter().map(move |response| Reading { opponent: opponent.clone(), response: response.clone() })
+    }).collect()
+}
+
+// The score under every reading that covers all the guide's symbols, best first
+pub fn decode_guide(rules: &Rules, guide: &Guide) -> Vec<Decoding> {
+    let mut decodings: Vec<Decoding> = readings(rules).into_iter()
+        .filter_map(|reading| Some(Decoding { score: score_guide(rules, &reading, guide)?, reading }))
+        .collect();
+    decodings.sort_by_key(|d| Reverse(d.score));
+    decodings
+}
+
+pub fn matching_target(decodings: &[Decoding], target: u32) -> Vec<&Decoding> {
+    decodings.iter().filter(|d| d.score == target).collect()
+}
+
+pub fn turn_score(rules: &Rules, reading: &Reading, turn: (char, char)) -> Option<u32> {
+    let (them, you) = reading.decode(rules, turn)?;
+    Some(rules.score(you, them))
+}
+
+pub fn score_guide(rules: &Rules, reading: &Reading, guide: &Guide) -> Option<u32> {
+    guide.iter().map(|t| turn_score(rules, reading, *t)).sum()
+}
+
+fn game_score(guide: &Guide) -> u32 {
+    let rules = Rules::rock_paper_scissors();
+    score_guide(&rules, &Reading::hands(&rules), guide).expect("read_guide only accepts A-C and X-Z")
+}
+
+pub fn guide_score(input: &str) -> Result<u32, ParseError> {
+    let g = read_guide(input)?;
+    Ok(game_score(&g))
+}
+
+fn true_game_score(guide: &Guide) -> u32 {
+    let rules = Rules::rock_paper_scissors();
+    score_guide(&rules, &Reading::outcomes(&rules), guide).expect("read_guide only accepts A-C and X-Z")
+}
+
+pub fn true_guide_score(input: &str) -> Result<u32, ParseError> {
+    let g = read_guide(input)?;
+    Ok(true_game_score(&g))
+}
+
+pub struct RockPaperScissors;
+
+impl Solver for RockPaperScissors {
+    type Parsed = Guide;
+
+    fn parse(&self, input: &str) -> Result<Guide, ParseError> {
+        read_guide(input)
+    }
+
+    fn part1(&self, guide: &Guide) -> Answer {
+        game_score(guide).into()
+    }
+
+    fn part2(&self, guide: &Guide) -> Answer {
+        true_game_score(guide).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, RockPaperScissors, 15, 12)
 }
 
 #[cfg(test)]
@@ -81,15 +290,78 @@ mod tests {
 
     #[test]
     fn part_one() {
-        let input = "A Y\nB X\nC Z".to_string();
-        let score = guide_score(&input);
+        let score = guide_score(EXAMPLE).unwrap();
         assert_eq!(score, 15);
     }
 
     #[test]
     fn part_two() {
-        let input = "A Y\nB X\nC Z".to_string();
-        let score = true_guide_score(&input);
+        let score = true_guide_score(EXAMPLE).unwrap();
         assert_eq!(score, 12)
     }
+
+    #[test]
+    fn derived_responses() {
+        let rules = Rules::rock_paper_scissors();
+        let [rock, paper, scissors] = ["Rock", "Paper", "Scissors"].map(|h| rules.hand(h).unwrap());
+        assert_eq!(rules.outcome(paper, rock), Outcome::Win);
+        assert_eq!(rules.response(rock, Outcome::Lose), Some(scissors));
+        assert_eq!(rules.response(scissors, Outcome::Draw), Some(scissors));
+        assert_eq!(rules.play(rock, Response::Hand(3)), None);
+    }
+
+    #[test]
+    fn lizard_spock() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let [rock, spock] = ["Rock", "Spock"].map(|h| rules.hand(h).unwrap());
+        assert_eq!(rules.outcome(spock, rock), Outcome::Win);
+        // Paper and Spock both beat Rock, and Spock is worth more
+        assert_eq!(rules.response(rock, Outcome::Win), Some(spock));
+        assert_eq!(rules.score(rock, spock), 1);
+
+        // Only the first three hands have symbols in the guide
+        let guide = vec![('A', 'Y'), ('C', 'Z')];
+        assert_eq!(score_guide(&rules, &Reading::hands(&rules), &guide), Some(8 + 6));
+        assert_eq!(score_guide(&rules, &Reading::outcomes(&rules), &guide), Some(4 + 11));
+    }
+
+    #[test]
+    fn custom_rules() {
+        let rules = Rules::new(&[("Stone", 10), ("Sheet", 0)], &[("Sheet", "Stone")], [1, 2, 3]).unwrap();
+        let guide = vec![('A', 'X'), ('B', 'Y')];
+        assert_eq!(score_guide(&rules, &Reading::hands(&rules), &guide), Some(12 + 2));
+
+        let circular = Rules::new(&[("Rock", 1), ("Paper", 2)], &[("Rock", "Paper"), ("Paper", "Rock")], [0, 3, 6]);
+        assert_eq!(circular, Err("`Paper` and `Rock` both beat each other".to_string()));
+    }
+
+    #[test]
+    fn decoding_guide() {
+        let rules = Rules::rock_paper_scissors();
+        let guide = read_guide(EXAMPLE).unwrap();
+        let decodings = decode_guide(&rules, &guide);
+        assert_eq!(decodings.len(), 6 * (6 + 6));
+
+        // Both puzzle readings are in there
+        let scored = |reading: Reading| decodings.iter().find(|d| d.reading == reading).map(|d| d.score);
+        assert_eq!(scored(Reading::hands(&rules)), Some(15));
+        assert_eq!(scored(Reading::outcomes(&rules)), Some(12));
+
+        // At best every round is a win, playing each hand once
+        assert_eq!(decodings[0].score, (1 + 2 + 3) + 3 * 6);
+        assert!(matching_target(&decodings, 15).contains(&&Decoding { reading: Reading::hands(&rules), score: 15 }));
+        assert!(matching_target(&decodings, 1000).is_empty());
+    }
+
+    #[test]
+    fn describing_readings() {
+        let rules = Rules::rock_paper_scissors();
+        assert_eq!(Reading::outcomes(&rules).describe(&rules), "A=Rock B=Paper C=Scissors X=Lose Y=Draw Z=Win");
+    }
+
+    #[test]
+    fn unknown_response() {
+        let error = read_guide("A Y\nB W\nC Z").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
 }
\ No newline at end of file
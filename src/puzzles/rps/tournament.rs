@@ -0,0 +1,354 @@
+use std::cmp::Reverse;
+use std::fmt;
+use crate::puzzles::rps::{Guide, Hand, Outcome, Reading, Rules};
+
+// xorshift64*, so seeded runs repeat exactly without needing a dependency
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rng {
+    state: u64
+}
+
+impl Rng {
+    // Scrambles the seed with a splitmix64 step, so nearby seeds start far apart
+    pub fn new(seed: u64) -> Self {
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        // A zero state would stay zero forever
+        Rng { state: if z == 0 { 0x9e37_79b9_7f4a_7c15 } else { z } }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    // Close enough to uniform for the handful of hands a game has
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    fn play(&mut self, rules: &Rules) -> Hand;
+
+    // Told after each round what both sides played
+    fn observe(&mut self, _mine: Hand, _theirs: Hand) {}
+
+    // Forgets everything before the next match
+    fn reset(&mut self) {}
+}
+
+// The hand that beats `predicted`, or failing that draws with it
+fn counter(rules: &Rules, predicted: Hand) -> Hand {
+    rules.response(predicted, Outcome::Win)
+        .or_else(|| rules.response(predicted, Outcome::Draw))
+        .unwrap_or(predicted)
+}
+
+fn most_common(counts: &[u32]) -> Option<Hand> {
+    let (hand, count) = counts.iter().enumerate().max_by_key(|(h, c)| (**c, Reverse(*h)))?;
+    (*count > 0).then_some(hand)
+}
+
+fn count(counts: &mut Vec<u32>, hand: Hand) {
+    if counts.len() <= hand {
+        counts.resize(hand + 1, 0);
+    }
+    counts[hand] += 1;
+}
+
+// Plays a fixed list of hands, starting again from the top when it runs out
+pub struct Replay {
+    name: String,
+    hands: Vec<Hand>,
+    next: usize
+}
+
+impl Replay {
+    pub fn new(name: &str, hands: Vec<Hand>) -> Self {
+        Replay { name: name.to_string(), hands, next: 0 }
+    }
+
+    // The elf's side of the guide, or the opponent's if `opponent` is set
+    pub fn guide(rules: &Rules, reading: &Reading, guide: &Guide, opponent: bool) -> Option<Self> {
+        let hands = guide.iter()
+            .map(|t| reading.decode(rules, *t).map(|(them, you)| if opponent { them } else { you }))
+            .collect::<Option<Vec<Hand>>>()?;
+        let name = if opponent { "guide (opponent)" } else { "guide" };
+        Some(Replay::new(name, hands))
+    }
+}
+
+impl Strategy for Replay {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn play(&mut self, _rules: &Rules) -> Hand {
+        let hand = self.hands.get(self.next).copied().unwrap_or(0);
+        self.next = (self.next + 1) % self.hands.len().max(1);
+        hand
+    }
+
+    fn reset(&mut self) {
+        self.next = 0;
+    }
+}
+
+// Assumes the opponent will play whatever they've played most so far
+#[derive(Default)]
+pub struct Frequency {
+    counts: Vec<u32>
+}
+
+impl Strategy for Frequency {
+    fn name(&self) -> String {
+        "frequency".to_string()
+    }
+
+    fn play(&mut self, rules: &Rules) -> Hand {
+        most_common(&self.counts).map_or(0, |h| counter(rules, h))
+    }
+
+    fn observe(&mut self, _mine: Hand, theirs: Hand) {
+        count(&mut self.counts, theirs);
+    }
+
+    fn reset(&mut self) {
+        self.counts.clear();
+    }
+}
+
+pub struct Random {
+    seed: u64,
+    rng: Rng
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Random { seed, rng: Rng::new(seed) }
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        format!("random ({})", self.seed)
+    }
+
+    fn play(&mut self, rules: &Rules) -> Hand {
+        self.rng.below(rules.hands())
+    }
+
+    fn reset(&mut self) {
+        self.rng = Rng::new(self.seed);
+    }
+}
+
+// Predicts the opponent's next hand from what they've followed their last hand with before
+#[derive(Default)]
+pub struct Markov {
+    // After each hand, how often each hand came next
+    transitions: Vec<Vec<u32>>,
+    last: Option<Hand>
+}
+
+impl Strategy for Markov {
+    fn name(&self) -> String {
+        "markov".to_string()
+    }
+
+    fn play(&mut self, rules: &Rules) -> Hand {
+        let predicted = self.last
+            .and_then(|last| self.transitions.get(last))
+            .and_then(|next| most_common(next));
+        predicted.map_or(0, |h| counter(rules, h))
+    }
+
+    fn observe(&mut self, _mine: Hand, theirs: Hand) {
+        if let Some(last) = self.last {
+            if self.transitions.len() <= last {
+                self.transitions.resize(last + 1, Vec::new());
+            }
+            count(&mut self.transitions[last], theirs);
+        }
+        self.last = Some(theirs);
+    }
+
+    fn reset(&mut self) {
+        self.transitions.clear();
+        self.last = None;
+    }
+}
+
+// How one side did over some rounds
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Record {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub score: u64
+}
+
+impl Record {
+    pub fn rounds(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    fn rate(&self, n: u32) -> f64 {
+        match self.rounds() {
+            0 => 0.0,
+            rounds => n as f64 / rounds as f64
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        self.rate(self.wins)
+    }
+
+    pub fn draw_rate(&self) -> f64 {
+        self.rate(self.draws)
+    }
+
+    pub fn loss_rate(&self) -> f64 {
+        self.rate(self.losses)
+    }
+
+    fn add(&mut self, other: &Record) {
+        self.wins += other.wins;
+        self.draws += other.draws;
+        self.losses += other.losses;
+        self.score += other.score;
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "won {:.1}%, drew {:.1}%, lost {:.1}%, scored {}",
+            100.0 * self.win_rate(), 100.0 * self.draw_rate(), 100.0 * self.loss_rate(), self.score)
+    }
+}
+
+// Resets both strategies and plays `rounds` rounds between them, scoring each
+// round the way the guide is scored
+pub fn play_match(rules: &Rules, a: &mut dyn Strategy, b: &mut dyn Strategy, rounds: u32) -> (Record, Record) {
+    a.reset();
+    b.reset();
+    let (mut first, mut second) = (Record::default(), Record::default());
+
+    for _ in 0..rounds {
+        let (x, y) = (a.play(rules), b.play(rules));
+        for (record, you, them) in [(&mut first, x, y), (&mut second, y, x)] {
+            match rules.outcome(you, them) {
+                Outcome::Win => record.wins += 1,
+                Outcome::Draw => record.draws += 1,
+                Outcome::Lose => record.losses += 1
+            }
+            record.score += rules.score(you, them) as u64;
+        }
+        a.observe(x, y);
+        b.observe(y, x);
+    }
+    (first, second)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    pub record: Record
+}
+
+// Every strategy plays every other once, best total score first
+pub fn round_robin(rules: &Rules, players: &mut [Box<dyn Strategy>], rounds: u32) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = players.iter()
+        .map(|p| Standing { name: p.name(), record: Record::default() })
+        .collect();
+
+    for i in 0..players.len() {
+        for j in i + 1..players.len() {
+            let (left, right) = players.split_at_mut(j);
+            let (first, second) = play_match(rules, left[i].as_mut(), right[0].as_mut(), rounds);
+            standings[i].record.add(&first);
+            standings[j].record.add(&second);
+        }
+    }
+    standings.sort_by_key(|s| Reverse(s.record.score));
+    standings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::rps::{read_guide, EXAMPLE};
+
+    #[test]
+    fn seeded_rng() {
+        let rolls = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..20).map(|_| rng.below(3)).collect::<Vec<usize>>()
+        };
+        assert_eq!(rolls(7), rolls(7));
+        assert_ne!(rolls(7), rolls(8));
+        assert!(rolls(0).iter().all(|r| *r < 3));
+
+        // This seed used to cancel out to a zero state that only ever rolled 0
+        let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15);
+        assert!((0..20).any(|_| rng.next_u64() != 0));
+        assert_ne!(rolls(0x9e37_79b9_7f4a_7c15), vec![0; 20]);
+    }
+
+    #[test]
+    fn replaying_the_guide() {
+        let rules = Rules::rock_paper_scissors();
+        let guide = read_guide(EXAMPLE).unwrap();
+        let reading = Reading::hands(&rules);
+        let mut elf = Replay::guide(&rules, &reading, &guide, false).unwrap();
+        let mut opponent = Replay::guide(&rules, &reading, &guide, true).unwrap();
+
+        // The same three rounds as the puzzle, twice over
+        let (first, second) = play_match(&rules, &mut elf, &mut opponent, 6);
+        assert_eq!(first, Record { wins: 2, draws: 2, losses: 2, score: 2 * 15 });
+        assert_eq!(second.wins, first.losses);
+    }
+
+    #[test]
+    fn predicting_opponents() {
+        let rules = Rules::rock_paper_scissors();
+        let mut rocks = Replay::new("rocks", vec![0]);
+        let (frequency, _) = play_match(&rules, &mut Frequency::default(), &mut rocks, 100);
+        assert_eq!(frequency.wins, 99);
+
+        // Cycling through the hands fools counting but not following on from the last hand
+        let mut cycle = Replay::new("cycle", vec![0, 1, 2]);
+        let (frequency, _) = play_match(&rules, &mut Frequency::default(), &mut cycle, 300);
+        let (markov, _) = play_match(&rules, &mut Markov::default(), &mut cycle, 300);
+        assert!(frequency.win_rate() < 0.5);
+        assert!(markov.win_rate() > 0.9);
+    }
+
+    #[test]
+    fn tournament() {
+        let rules = Rules::rock_paper_scissors();
+        let mut players: Vec<Box<dyn Strategy>> = vec![
+            Box::new(Replay::new("cycle", vec![0, 1, 2])),
+            Box::new(Frequency::default()),
+            Box::new(Random::new(42)),
+            Box::new(Markov::default())
+        ];
+        let standings = round_robin(&rules, &mut players, 1000);
+        assert_eq!(standings.len(), 4);
+        assert!(standings.iter().all(|s| s.record.rounds() == 3 * 1000));
+
+        let wins: u32 = standings.iter().map(|s| s.record.wins).sum();
+        let losses: u32 = standings.iter().map(|s| s.record.losses).sum();
+        assert_eq!(wins, losses);
+
+        // Running it again gives the same results
+        assert_eq!(round_robin(&rules, &mut players, 1000), standings);
+    }
+}
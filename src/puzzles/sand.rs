@@ -1,8 +1,17 @@
 use std::collections::HashSet;
 use std::cmp::{min, max};
+use crate::error::ParseError;
+use crate::parsing::{lines, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
 
-type Coord = (i32, i32);
-type World = HashSet<Coord>; 
+const DAY: u8 = 14;
+
+pub const EXAMPLE: &str = r"498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+pub type Coord = (i32, i32);
+pub type World = HashSet<Coord>; 
 
 fn build_line(start: Coord, end: Coord) -> World {
     let (a, b) = start;
@@ -23,24 +32,31 @@ fn build_line(start: Coord, end: Coord) -> World {
 
 }
 
-fn read_rocks(input: &String) -> World {
+fn read_point(line: &SourceLine, p: &str) -> Result<Coord, ParseError> {
+    let (l, r) = p.split_once(',').ok_or_else(|| line.error(p, "expected a point like `498,4`"))?;
+    Ok((line.number(l)?, line.number(r)?))
+}
+
+pub fn read_rocks(input: &str) -> Result<World, ParseError> {
     let mut world: World = HashSet::new();
-    input.trim().lines().for_each(|line| {
-        let coords: Vec<Coord> = line.split(" -> ").map(|p| {
-            let mut parts = p.split(",");
-            let l: i32 = parts.next().unwrap().parse().unwrap();
-            let r: i32 = parts.next().unwrap().parse().unwrap();
-            (l, r)
-        }).collect();
+    for source in lines(DAY, input) {
+        let points: Vec<&str> = source.text.trim().split(" -> ").collect();
+        let coords: Vec<Coord> = points.iter().map(|p| read_point(&source, p)).collect::<Result<_, _>>()?;
 
         for i in 1..coords.len() {
             let left = coords[i-1];
             let right = coords[i];
+            if left.0 != right.0 && left.1 != right.1 {
+                return Err(source.error(points[i], "rock lines must be horizontal or vertical"));
+            }
             let line = build_line(left, right);
-            world = world.union(&line).map(|c| *c).collect();
+            world = world.union(&line).copied().collect();
         }
-    });
-    world
+    }
+    if world.is_empty() {
+        return Err(ParseError::input(DAY, "there are no rocks"));
+    }
+    Ok(world)
 }
 
 fn find_lowest_y(world: &World) -> i32{
@@ -88,12 +104,12 @@ fn simulate_sand(rock: &World, floor: bool) -> u32 {
     let mut sand: World = HashSet::new();
 
     let mut count: u32 = 0;
-    let low_point = find_lowest_y(&rock);
+    let low_point = find_lowest_y(rock);
 
     loop {
         count += 1;
 
-        match compute_sand_position(&rock, &sand, low_point, floor) {
+        match compute_sand_position(rock, &sand, low_point, floor) {
             Some(coord) => { 
                 if coord == (500, 0) {
                     return count;
@@ -105,14 +121,36 @@ fn simulate_sand(rock: &World, floor: bool) -> u32 {
     }
 }
 
-pub fn maximum_static_sand(input: &String) -> u32 {
-    let rock = read_rocks(input);
-    simulate_sand(&rock, false)
+pub fn maximum_static_sand(input: &str) -> Result<u32, ParseError> {
+    let rock = read_rocks(input)?;
+    Ok(simulate_sand(&rock, false))
+}
+
+pub fn maximum_floor_sand(input: &str) -> Result<u32, ParseError> {
+    let rock = read_rocks(input)?;
+    Ok(simulate_sand(&rock, true))
+}
+
+pub struct RegolithReservoir;
+
+impl Solver for RegolithReservoir {
+    type Parsed = World;
+
+    fn parse(&self, input: &str) -> Result<World, ParseError> {
+        read_rocks(input)
+    }
+
+    fn part1(&self, rock: &World) -> Answer {
+        simulate_sand(rock, false).into()
+    }
+
+    fn part2(&self, rock: &World) -> Answer {
+        simulate_sand(rock, true).into()
+    }
 }
 
-pub fn maximum_floor_sand(input: &String) -> u32 {
-    let rock = read_rocks(input);
-    simulate_sand(&rock, true)
+pub fn example() -> Example {
+    Example::new(EXAMPLE, RegolithReservoir, 24, 93)
 }
 
 #[cfg(test)]
@@ -121,20 +159,17 @@ mod tests {
 
     #[test]
     fn example_one() {
-        let input = r"
-498,4 -> 498,6 -> 496,6
-503,4 -> 502,4 -> 502,9 -> 494,9
-        ".to_string();
-
-        assert_eq!(maximum_static_sand(&input), 24);
+        assert_eq!(maximum_static_sand(EXAMPLE), Ok(24));
     }
 
     #[test]
     fn example_two() {
-        let input = r"
-498,4 -> 498,6 -> 496,6
-503,4 -> 502,4 -> 502,9 -> 494,9
-        ".to_string();
-        assert_eq!(maximum_floor_sand(&input), 93);
+        assert_eq!(maximum_floor_sand(EXAMPLE), Ok(93));
+    }
+
+    #[test]
+    fn diagonal_rock() {
+        let error = read_rocks("498,4 -> 498,6 -> 496,8").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 19));
     }
 }
\ No newline at end of file
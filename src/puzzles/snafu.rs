@@ -0,0 +1,214 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+use crate::error::ParseError;
+use crate::parsing::lines;
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 25;
+
+pub const EXAMPLE: &str = r"1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122";
+
+// Balanced base 5: each digit is worth -2 to 2, least significant first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snafu {
+    digits: Vec<i8>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnafuDigitError {
+    pub position: usize,
+    pub found: char
+}
+
+impl fmt::Display for SnafuDigitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}` is not a SNAFU digit, expected one of `=-012`", self.found)
+    }
+}
+
+impl std::error::Error for SnafuDigitError {}
+
+impl Snafu {
+    fn from_digits(mut digits: Vec<i8>) -> Self {
+        while digits.len() > 1 && digits.last() == Some(&0) {
+            digits.pop();
+        }
+        if digits.is_empty() {
+            digits.push(0);
+        }
+        Snafu { digits }
+    }
+
+    // Partial totals can step just outside the i64 range on the way to one inside it
+    pub fn to_i64(&self) -> Option<i64> {
+        let total = self.digits.iter().rev().try_fold(0i128, |total, d| total.checked_mul(5)?.checked_add(*d as i128))?;
+        i64::try_from(total).ok()
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = SnafuDigitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.char_indices().rev().map(|(position, c)| match c {
+            '=' => Ok(-2),
+            '-' => Ok(-1),
+            '0' => Ok(0),
+            '1' => Ok(1),
+            '2' => Ok(2),
+            found => Err(SnafuDigitError { position, found })
+        }).collect::<Result<Vec<i8>, _>>()?;
+        Ok(Snafu::from_digits(digits))
+    }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text: String = self.digits.iter().rev().map(|d| match d {
+            -2 => '=',
+            -1 => '-',
+            0 => '0',
+            1 => '1',
+            _ => '2'
+        }).collect();
+        write!(f, "{text}")
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(mut value: i64) -> Self {
+        let mut digits: Vec<i8> = Vec::new();
+        while value != 0 {
+            // A remainder of 3 or 4 becomes -2 or -1, carrying one into the next digit
+            let r = value.rem_euclid(5);
+            digits.push(if r > 2 { r - 5 } else { r } as i8);
+            value = value.div_euclid(5) + (r > 2) as i64;
+        }
+        Snafu::from_digits(digits)
+    }
+}
+
+impl Add for &Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: &Snafu) -> Snafu {
+        let length = self.digits.len().max(other.digits.len());
+        let mut digits: Vec<i8> = Vec::with_capacity(length + 1);
+        let mut carry: i8 = 0;
+
+        for i in 0..length {
+            let total = self.digits.get(i).unwrap_or(&0) + other.digits.get(i).unwrap_or(&0) + carry;
+            // total is between -5 and 5, so at most one carry either way
+            carry = match total {
+                3.. => 1,
+                ..=-3 => -1,
+                _ => 0
+            };
+            digits.push(total - 5 * carry);
+        }
+        digits.push(carry);
+        Snafu::from_digits(digits)
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: Snafu) -> Snafu {
+        &self + &other
+    }
+}
+
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Snafu>>(iter: I) -> Snafu {
+        iter.fold(Snafu::from(0), |total, n| &total + n)
+    }
+}
+
+pub fn read_fuel(input: &str) -> Result<Vec<Snafu>, ParseError> {
+    lines(DAY, input).map(|line| {
+        let text = line.text.trim();
+        text.parse::<Snafu>().map_err(|e| line.error(&text[e.position..], e.to_string()))
+    }).collect()
+}
+
+fn fuel_total(fuel: &[Snafu]) -> Snafu {
+    fuel.iter().sum()
+}
+
+pub fn console_number(input: &str) -> Result<String, ParseError> {
+    Ok(fuel_total(&read_fuel(input)?).to_string())
+}
+
+pub struct FullOfHotAir;
+
+impl Solver for FullOfHotAir {
+    type Parsed = Vec<Snafu>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Snafu>, ParseError> {
+        read_fuel(input)
+    }
+
+    fn part1(&self, fuel: &Vec<Snafu>) -> Answer {
+        fuel_total(fuel).to_string().into()
+    }
+
+    // The last day only has the one puzzle
+    fn part2(&self, _fuel: &Vec<Snafu>) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, FullOfHotAir, "2=-1=0".to_string(), Answer::Unsolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        let pairs = [(1, "1"), (3, "1="), (8, "2="), (20, "1-0"), (2022, "1=11-2"), (314159265, "1121-1110-1=0"), (0, "0"), (-7, "-="), (i64::MAX, "1110--=-02=100==0-0=11=11212"), (i64::MIN, "---011210=2-00220102--2--==2")];
+        for (decimal, snafu) in pairs {
+            assert_eq!(Snafu::from(decimal).to_string(), snafu);
+            assert_eq!(snafu.parse::<Snafu>().unwrap().to_i64(), Some(decimal));
+        }
+    }
+
+    #[test]
+    fn adding() {
+        let a: Snafu = "2=".parse().unwrap();
+        let b: Snafu = "1-0".parse().unwrap();
+        assert_eq!((&a + &b).to_i64(), Some(28));
+        assert_eq!(("2".parse::<Snafu>().unwrap() + "=".parse().unwrap()).to_string(), "0");
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(console_number(EXAMPLE), Ok("2=-1=0".to_string()));
+        let total: i64 = read_fuel(EXAMPLE).unwrap().iter().map(|s| s.to_i64().unwrap()).sum();
+        assert_eq!(total, 4890);
+    }
+
+    #[test]
+    fn bad_digit() {
+        let error = read_fuel("1=-0-2\n12311").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+}
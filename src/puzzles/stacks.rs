@@ -1,116 +1,158 @@
 use std::collections::HashMap;
+use crate::error::ParseError;
+use crate::parsing::{groups, SourceLine};
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
 
-type Stacks = HashMap<u8, Vec<char>>;
-type Instruction = (u8, u8, u8);
+const DAY: u8 = 5;
 
-fn read_arrangement(input: &String) -> (Stacks, Vec<Instruction>) {
-    let vectored: Vec<&str> = input.lines().collect();
-    let grouped: Vec<&[&str]> = vectored.split(|s| *s == "").collect();
+pub const EXAMPLE: &str = r"    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
 
-    let boxes: Vec<&str> = grouped.get(0).expect("Must have a box arrangement").iter().map(|l|*l).collect();
-    let stack = read_boxes(boxes);
+pub type Stacks = HashMap<u8, Vec<char>>;
+pub type Instruction = (u8, u8, u8);
+pub type Arrangement = (Stacks, Vec<Instruction>);
 
-    let is: Vec<&str> = grouped.get(1).unwrap().iter().map(|l|*l).collect();
-    let instructions = read_instructions(is);
-    (stack, instructions)
+pub fn read_arrangement(input: &str) -> Result<Arrangement, ParseError> {
+    let grouped = groups(DAY, input);
+
+    let boxes = grouped.first().ok_or_else(|| ParseError::input(DAY, "missing the box arrangement"))?;
+    let stack = read_boxes(boxes)?;
+
+    let is = grouped.get(1).ok_or_else(|| ParseError::input(DAY, "missing the move instructions"))?;
+    let instructions = read_instructions(is, &stack)?;
+    Ok((stack, instructions))
 }
 
-fn read_boxes(input: Vec<&str>) -> Stacks{
-    let indexes = input.iter().rev().next().expect("Must have an element");
+fn read_boxes(input: &[SourceLine]) -> Result<Stacks, ParseError> {
+    let indexes = input.last().expect("Groups are never empty");
     let mut index_map: HashMap<usize, u8> = HashMap::new();
-    let index_list = indexes.char_indices().filter(|(_, c)| *c!=' ');
-
-    index_list.clone().for_each(|(i, c)| { index_map.insert(i, c.to_digit(10).expect("Must be a digit") as u8);});
+    let index_list = indexes.text.char_indices().filter(|(_, c)| *c!=' ');
+
+    // Stacks are numbered 1, 2, 3... from the left
+    for (n, (i, c)) in index_list.clone().enumerate() {
+        let digit = c.to_digit(10)
+            .ok_or_else(|| indexes.error(&indexes.text[i..], format!("expected a stack number, found `{c}`")))?;
+        if digit as usize != n + 1 {
+            return Err(indexes.error(&indexes.text[i..], format!("expected stack {}, found {digit}", n + 1)));
+        }
+        index_map.insert(i, digit as u8);
+    }
 
     let char_indexes: Vec<usize> = index_list.map(|(i, _)| i).collect();
 
-    let mut stacks: HashMap<u8, Vec<char>> = HashMap::new();
+    let mut stacks: HashMap<u8, Vec<char>> = index_map.values().map(|i| (*i, Vec::new())).collect();
 
     input.iter().rev().skip(1).for_each(|line| {
-        let cs: Vec<char> = line.chars().collect();
+        let cs: Vec<char> = line.text.chars().collect();
         for index in &char_indexes {
-            match cs.get(*index) {
-                Some(c) => {
-                    if *c==' ' {continue;} 
-                    let true_index = index_map.get(index).expect("Index translation must exist");
-                    match stacks.get_mut(true_index) {
-                        Some(v) => v.push(*c),
-                        None => { stacks.insert(*true_index, vec![*c]); }
-                    }
-                },
-                None => ()
+            if let Some(c) = cs.get(*index) {
+                if *c==' ' {continue;} 
+                let true_index = index_map.get(index).expect("Index translation must exist");
+                stacks.get_mut(true_index).expect("Every index has a stack").push(*c);
             }
         }
     });
-    stacks
+    Ok(stacks)
 }
 
-fn read_instructions(input: Vec<&str>) -> Vec<Instruction>{
+// Follows how tall each stack gets, since both cranes move the same number of crates
+fn read_instructions(input: &[SourceLine], stacks: &Stacks) -> Result<Vec<Instruction>, ParseError> {
+    let mut heights: HashMap<u8, usize> = stacks.iter().map(|(i, s)| (*i, s.len())).collect();
     input.iter().map(|line| {
-        let ls: Vec<&str> = line.split(" ").collect();
-        let count: u8 = ls.get(1).unwrap().parse().unwrap();
-        let origin: u8 = ls.get(3).unwrap().parse().unwrap();
-        let destination: u8 = ls.get(5).unwrap().parse().unwrap();
-        (count, origin, destination)
+        let ls: Vec<&str> = line.text.split(' ').collect();
+        let count_word = line.word(&ls, 1, "a crate count")?;
+        let count: u8 = line.number(count_word)?;
+        let origin_word = line.word(&ls, 3, "a stack to move from")?;
+        let destination_word = line.word(&ls, 5, "a stack to move to")?;
+        let origin: u8 = line.number(origin_word)?;
+        let destination: u8 = line.number(destination_word)?;
+        for (stack, word) in [(origin, origin_word), (destination, destination_word)] {
+            if !stacks.contains_key(&stack) {
+                return Err(line.error(word, format!("there is no stack {stack}")));
+            }
+        }
+        let height = heights[&origin];
+        if count as usize > height {
+            return Err(line.error(count_word, format!("stack {origin} only has {height} crates to move")));
+        }
+        *heights.get_mut(&origin).expect("Checked above") -= count as usize;
+        *heights.get_mut(&destination).expect("Checked above") += count as usize;
+        Ok((count, origin, destination))
     }).collect()
 }
 
+// Takes the top `count` crates, or as many as there are
+fn lift(stacks: &mut Stacks, origin: u8, count: u8) -> Vec<char> {
+    let stack = stacks.get_mut(&origin).expect("origin index must exist");
+    stack.split_off(stack.len().saturating_sub(count as usize))
+}
+
 fn process(stacks: &mut Stacks, instruction: Instruction) {
     let (count, origin, destination) = instruction;
+    let mut moved = lift(stacks, origin, count);
 
-    for _ in 0..count {
-        let move_me = stacks.get_mut(&origin).expect("origin index must exist").pop().unwrap();
-        stacks.get_mut(&destination).expect("Destination index must exist").push(move_me);
-    }
+    // One at a time, so the order flips
+    moved.reverse();
+    stacks.get_mut(&destination).expect("Destination index must exist").extend(moved);
 }
 
 fn process_9001(stacks: &mut Stacks, instruction: Instruction) {
     let (count, origin, destination) = instruction;
-    let mut temp: Vec<char> = Vec::new();
+    let moved = lift(stacks, origin, count);
 
-    for _ in 0..count {
-        let move_me = stacks.get_mut(&origin).expect("origin index must exist").pop().unwrap();
-        temp.push(move_me);
-    }
-
-    while temp.len() > 0 {
-        let move_me = temp.pop().unwrap();
-        stacks.get_mut(&destination).expect("Destination index must exist").push(move_me);
-    }
+    stacks.get_mut(&destination).expect("Destination index must exist").extend(moved);
 }
 
-pub fn full_process(input: &String) -> String {
-    let (mut stacks, instructions) = read_arrangement(input);
+fn run_crane(arrangement: &Arrangement, step: fn(&mut Stacks, Instruction)) -> String {
+    let (stacks, instructions) = arrangement;
+    let mut stacks = stacks.clone();
 
     for instruction in instructions {
-        process(&mut stacks, instruction);
+        step(&mut stacks, *instruction);
     }
 
-    let mut output: Vec<char> = Vec::new();
+    // An empty stack has nothing on top to read
+    (1..=stacks.len())
+        .filter_map(|i| stacks.get(&(i as u8))?.last())
+        .collect()
+}
 
-    for i in 1..=stacks.len() {
-        let c = stacks.get(&(i as u8)).unwrap().last().unwrap();
-        output.push(*c);
-    }
+pub fn full_process(input: &str) -> Result<String, ParseError> {
+    Ok(run_crane(&read_arrangement(input)?, process))
+}
 
-    output.iter().collect()
+pub fn full_process_9001(input: &str) -> Result<String, ParseError> {
+    Ok(run_crane(&read_arrangement(input)?, process_9001))
 }
 
-pub fn full_process_9001(input: &String) -> String {
-    let (mut stacks, instructions) = read_arrangement(input);
+pub struct SupplyStacks;
 
-    for instruction in instructions {
-        process_9001(&mut stacks, instruction);
+impl Solver for SupplyStacks {
+    type Parsed = Arrangement;
+
+    fn parse(&self, input: &str) -> Result<Arrangement, ParseError> {
+        read_arrangement(input)
     }
 
-    let mut output: Vec<char> = Vec::new();
+    fn part1(&self, arrangement: &Arrangement) -> Answer {
+        run_crane(arrangement, process).into()
+    }
 
-    for i in 1..=stacks.len() {
-        let c = stacks.get(&(i as u8)).unwrap().last().unwrap();
-        output.push(*c);
+    fn part2(&self, arrangement: &Arrangement) -> Answer {
+        run_crane(arrangement, process_9001).into()
     }
+}
 
-    output.iter().collect()
+pub fn example() -> Example {
+    Example::new(EXAMPLE, SupplyStacks, "CMZ".to_string(), "MCD".to_string())
 }
 
 #[cfg(test)]
@@ -119,35 +161,47 @@ mod tests {
 
     #[test]
     fn part_one() {
-        let input = r"    [D]    
-[N] [C]    
-[Z] [M] [P]
- 1   2   3 
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2".to_string();
-
-        let s = full_process(&input);
+        let s = full_process(EXAMPLE).unwrap();
 
         assert_eq!(s, "CMZ".to_string());
     }
 
     #[test]
     fn part_two() {
+        let s = full_process_9001(EXAMPLE).unwrap();
+
+        assert_eq!(s, "MCD".to_string());
+    }
+
+    #[test]
+    fn missing_stack() {
         let input = r"    [D]    
 [N] [C]    
 [Z] [M] [P]
  1   2   3 
 
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2".to_string();
+move 1 from 2 to 4";
 
-        let s = full_process_9001(&input);
+        let error = read_arrangement(input).unwrap_err();
+        assert_eq!((error.line, error.column), (6, 18));
+    }
 
-        assert_eq!(s, "MCD".to_string());
+    #[test]
+    fn too_many_crates() {
+        let input = EXAMPLE.replace("move 3 from 1 to 3", "move 9 from 2 to 1");
+        let error = read_arrangement(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (7, 6));
+        assert_eq!(error.reason, "stack 2 only has 2 crates to move");
+    }
+
+    #[test]
+    fn skipped_label() {
+        let error = read_arrangement("[A]     [B]\n 1   3 \n\nmove 1 from 1 to 3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+    }
+
+    #[test]
+    fn emptied_stack() {
+        assert_eq!(full_process("[A] [B]\n 1   2 \n\nmove 1 from 1 to 2"), Ok("A".to_string()));
     }
 }
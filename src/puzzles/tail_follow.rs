@@ -1,29 +1,46 @@
 use std::collections::HashSet;
-enum Direction {
+use crate::error::ParseError;
+use crate::parsing::lines;
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 9;
+
+pub const EXAMPLE: &str = r"R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+
+#[derive(Debug, Clone, Copy)]
+pub enum Direction {
     Right,
     Left,
     Up,
     Down
 }
 
-type Motion = (Direction, i32);
+pub type Motion = (Direction, i32);
 type Position = (i32, i32);
 type World = (Position, Position, HashSet<Position>);
 type ManyTailWorld = (Position, Vec<Position>, HashSet<Position>);
 
-fn parse_motions(input: &String) -> Vec<Motion>{
+pub fn parse_motions(input: &str) -> Result<Vec<Motion>, ParseError> {
     use Direction::*;
-    input.trim().lines().map(|l| {
-        let mut parts = l.split(" ");
-        let dir = match parts.next().unwrap() {
+    lines(DAY, input).map(|line| {
+        let parts: Vec<&str> = line.text.trim().split(' ').collect();
+        let dir = match line.word(&parts, 0, "a direction")? {
             "R" => Right,
             "L" => Left,
             "U" => Up,
             "D" => Down,
-            n => panic!("Unrecognized direction {n}")
+            n => return Err(line.error(n, format!("unrecognised direction `{n}`, expected R, L, U or D")))
         };
-        let distance: i32 = parts.next().unwrap().parse().unwrap();
-        (dir, distance)
+        let distance: i32 = line.number(line.word(&parts, 1, "a distance")?)?;
+        Ok((dir, distance))
     }).collect()
 }
 
@@ -123,66 +140,74 @@ fn update_many_tail_world(world: ManyTailWorld, motion: Motion) -> ManyTailWorld
     ((head_x, head_y), tails, visited)
 }
 
-pub fn tail_visited_positions(input: &String) -> u32 {
-    let motions = parse_motions(input);
+fn follow_single_tail(motions: &[Motion]) -> u32 {
     let mut world: World = ((0, 0), (0, 0), HashSet::new());
 
     for motion in motions {
-        world = update_world(world, motion);
+        world = update_world(world, *motion);
     }
 
     let (_, _, visited) = world;
     visited.len() as u32
 }
 
-pub fn many_tail_visited_positions(input: &String) -> u32 {
-    let motions = parse_motions(input);
+fn follow_many_tails(motions: &[Motion]) -> u32 {
     let head = (0, 0);
     let tails: Vec<Position> = (0..9).map(|_| (0, 0)).collect();
 
     let mut world: ManyTailWorld = (head, tails, HashSet::new());
 
     for motion in motions {
-        world = update_many_tail_world(world, motion);
+        world = update_many_tail_world(world, *motion);
     }
 
     let (_, _, visited) = world;
     visited.len() as u32
 }
 
+pub fn tail_visited_positions(input: &str) -> Result<u32, ParseError> {
+    Ok(follow_single_tail(&parse_motions(input)?))
+}
+
+pub fn many_tail_visited_positions(input: &str) -> Result<u32, ParseError> {
+    Ok(follow_many_tails(&parse_motions(input)?))
+}
+
+pub struct RopeBridge;
+
+impl Solver for RopeBridge {
+    type Parsed = Vec<Motion>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Motion>, ParseError> {
+        parse_motions(input)
+    }
+
+    fn part1(&self, motions: &Vec<Motion>) -> Answer {
+        follow_single_tail(motions).into()
+    }
+
+    fn part2(&self, motions: &Vec<Motion>) -> Answer {
+        follow_many_tails(motions).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, RopeBridge, 13, 1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn part_one() {
-        let input = r"
-R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2
-        ".to_string();
-        assert_eq!(tail_visited_positions(&input), 13);
+        assert_eq!(tail_visited_positions(EXAMPLE), Ok(13));
     }
 
     #[test]
 
     fn part_two_short() {
-        let input = r"
-R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2
-        ".to_string();
-        assert_eq!(many_tail_visited_positions(&input), 1);
+        assert_eq!(many_tail_visited_positions(EXAMPLE), Ok(1));
     }
 
     #[test]
@@ -198,6 +223,12 @@ L 25
 U 20
     ".to_string();
 
-    assert_eq!(many_tail_visited_positions(&input), 36);
+    assert_eq!(many_tail_visited_positions(&input), Ok(36));
+    }
+
+    #[test]
+    fn bad_direction() {
+        let error = parse_motions("R 4\nX 4").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
     }
 }
@@ -1,10 +1,38 @@
 
-type TreeGrid = Vec<Vec<u32>>;
+use crate::error::ParseError;
+use crate::parsing::lines;
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
 
-fn read_tree_grid(input: &String) -> TreeGrid{
-    input.trim().lines().map(|l| {
-        l.chars().map(|c| c.to_digit(10).unwrap()).collect()
-    }).collect()
+const DAY: u8 = 8;
+
+pub const EXAMPLE: &str = r"30373
+25512
+65332
+33549
+35390";
+
+pub type TreeGrid = Vec<Vec<u32>>;
+
+pub fn read_tree_grid(input: &str) -> Result<TreeGrid, ParseError> {
+    let mut width: Option<usize> = None;
+    let grid: TreeGrid = lines(DAY, input).map(|line| {
+        let text = line.text.trim();
+        let row = text.char_indices().map(|(i, c)| {
+            c.to_digit(10).ok_or_else(|| line.error(&text[i..], format!("expected a tree height, found `{c}`")))
+        }).collect::<Result<Vec<u32>, ParseError>>()?;
+        match width {
+            Some(w) if w != row.len() => Err(line.error(text, format!("expected {w} trees in the row, found {}", row.len()))),
+            _ => {
+                width = Some(row.len());
+                Ok(row)
+            }
+        }
+    }).collect::<Result<_, _>>()?;
+    if grid.is_empty() {
+        return Err(ParseError::input(DAY, "there are no trees"));
+    }
+    Ok(grid)
 }
 
 fn can_see_edge(row: usize, column: usize, grid: &TreeGrid) -> bool{
@@ -20,10 +48,9 @@ fn can_see_edge(row: usize, column: usize, grid: &TreeGrid) -> bool{
     left || right || up || down
 }
 
-fn trees_on_path(path: &Vec<(usize, usize)>, grid: &TreeGrid, start_value: u32) -> u32 {
+fn trees_on_path(path: &[(usize, usize)], grid: &TreeGrid, start_value: u32) -> u32 {
     let mut seen_count: u32 = 0;
-    for i in 0..path.len() {
-        let (row, column) = path[i];
+    for (row, column) in path.iter().copied() {
         let seen_value = grid[row][column];
         if seen_value < start_value {
             seen_count += 1;
@@ -40,12 +67,12 @@ fn scenic_score(row: usize, column: usize, grid: &TreeGrid) -> u32 {
     let height = grid.len();
     let width = grid[row].len();
 
-    let quick_trees = |path: &Vec<(usize, usize)>| trees_on_path(path, grid, value);
+    let quick_trees = |path: Vec<(usize, usize)>| trees_on_path(&path, grid, value);
 
-    let left    = quick_trees(&(0..column).rev().map(|temp_column| (row, temp_column)).collect());
-    let right   = quick_trees(&(column+1..width).map(|temp_column| (row, temp_column)).collect());
-    let up      = quick_trees(&(0..row).rev().map(|temp_row| (temp_row, column)).collect());
-    let down    = quick_trees(&(row+1..height).map(|temp_row| (temp_row, column)).collect());
+    let left    = quick_trees((0..column).rev().map(|temp_column| (row, temp_column)).collect());
+    let right   = quick_trees((column+1..width).map(|temp_column| (row, temp_column)).collect());
+    let up      = quick_trees((0..row).rev().map(|temp_row| (temp_row, column)).collect());
+    let down    = quick_trees((row+1..height).map(|temp_row| (temp_row, column)).collect());
 
     left * right * up * down
 }
@@ -56,45 +83,64 @@ fn count_edgeview_trees(grid: &TreeGrid) -> u32{
     ).sum()
 }
 
-pub fn count_visible_trees(input: &String) -> u32 {
-    let grid = read_tree_grid(input);
-    count_edgeview_trees(&grid)
-}
-
-pub fn best_scenic_score(input: &String) -> u32{
-    let grid = read_tree_grid(input);
+fn find_best_scenic_score(grid: &TreeGrid) -> u32 {
     grid.iter().enumerate().map(|(row, cells)|
-        cells.iter().enumerate().map(|(column, _value)| scenic_score(row, column, &grid)).max().unwrap())
+        cells.iter().enumerate().map(|(column, _value)| scenic_score(row, column, grid)).max().unwrap())
         .max().unwrap()
 }
 
+pub fn count_visible_trees(input: &str) -> Result<u32, ParseError> {
+    let grid = read_tree_grid(input)?;
+    Ok(count_edgeview_trees(&grid))
+}
+
+pub fn best_scenic_score(input: &str) -> Result<u32, ParseError> {
+    let grid = read_tree_grid(input)?;
+    Ok(find_best_scenic_score(&grid))
+}
+
+pub struct TreetopTreeHouse;
+
+impl Solver for TreetopTreeHouse {
+    type Parsed = TreeGrid;
+
+    fn parse(&self, input: &str) -> Result<TreeGrid, ParseError> {
+        read_tree_grid(input)
+    }
+
+    fn part1(&self, grid: &TreeGrid) -> Answer {
+        count_edgeview_trees(grid).into()
+    }
+
+    fn part2(&self, grid: &TreeGrid) -> Answer {
+        find_best_scenic_score(grid).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, TreetopTreeHouse, 21, 8)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn part_one() {
-        let input = r"
-30373
-25512
-65332
-33549
-35390".to_string();
-
-        let grid = read_tree_grid(&input);
+        let grid = read_tree_grid(EXAMPLE).unwrap();
         let edge_count = count_edgeview_trees(&grid);
         assert_eq!(edge_count, 21);
     }
 
     #[test]
     fn part_two() {
-        let input = r"
-30373
-25512
-65332
-33549
-35390".to_string();
+        assert_eq!(best_scenic_score(EXAMPLE), Ok(8));
+    }
 
-        assert_eq!(best_scenic_score(&input), 8);
+    #[test]
+    fn ragged_grid() {
+        let error = read_tree_grid("303\n25\n653").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.reason, "expected 3 trees in the row, found 2");
     }
 }
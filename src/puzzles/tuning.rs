@@ -1,14 +1,34 @@
 use std::collections::HashSet;
+use crate::error::ParseError;
+use crate::parsing::lines;
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
 
-pub fn first_marker(input: &String, length: u32) -> Option<u32> {
+const DAY: u8 = 6;
+
+pub const EXAMPLE: &str = r"mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+pub fn read_signal(input: &str) -> Result<String, ParseError> {
+    let line = lines(DAY, input).next().ok_or_else(|| ParseError::input(DAY, "the signal is empty"))?;
+    let signal = line.text.trim();
+    if let Some((i, c)) = signal.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        return Err(line.error(&signal[i..], format!("unexpected `{c}` in the signal")));
+    }
+    Ok(signal.to_string())
+}
+
+pub fn first_marker(input: &str, length: u32) -> Option<u32> {
     let length = length as usize;
+    if length == 0 || input.len() < length {
+        return None;
+    }
     for i in 0..input.len()-(length-1) {
         let part = &input[i..=i+(length-1)];
         if all_chars_differ(part) {
             return Some((i+length) as u32);
         }
     }
-    return None;
+    None
 }
 
 fn all_chars_differ(input: &str) -> bool{
@@ -16,25 +36,53 @@ fn all_chars_differ(input: &str) -> bool{
     char_set.len() == input.len()
 }
 
+pub struct TuningTrouble;
+
+impl Solver for TuningTrouble {
+    type Parsed = String;
+
+    fn parse(&self, input: &str) -> Result<String, ParseError> {
+        read_signal(input)
+    }
+
+    fn part1(&self, signal: &String) -> Answer {
+        first_marker(signal, 4).into()
+    }
+
+    fn part2(&self, signal: &String) -> Answer {
+        first_marker(signal, 14).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, TuningTrouble, 7, 19)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn part_one() {
-        assert_eq!(first_marker(&"mjqjpqmgbljsphdztnvjfqwrcgsmlb".to_string(), 4),     Some(7));
-        assert_eq!(first_marker(&"bvwbjplbgvbhsrlpgdmjqwftvncz".to_string(), 4),       Some(5));
-        assert_eq!(first_marker(&"nppdvjthqldpwncqszvftbrmjlhg".to_string(), 4),       Some(6));
-        assert_eq!(first_marker(&"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".to_string(), 4),  Some(10));
-        assert_eq!(first_marker(&"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".to_string(), 4),   Some(11));
+        assert_eq!(first_marker(EXAMPLE, 4),                              Some(7));
+        assert_eq!(first_marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 4),       Some(5));
+        assert_eq!(first_marker("nppdvjthqldpwncqszvftbrmjlhg", 4),       Some(6));
+        assert_eq!(first_marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4),  Some(10));
+        assert_eq!(first_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4),   Some(11));
     }
 
     #[test]
     fn part_two() {
-        assert_eq!(first_marker(&"mjqjpqmgbljsphdztnvjfqwrcgsmlb".to_string(), 14),     Some(19));
-        assert_eq!(first_marker(&"bvwbjplbgvbhsrlpgdmjqwftvncz".to_string(), 14),       Some(23));
-        assert_eq!(first_marker(&"nppdvjthqldpwncqszvftbrmjlhg".to_string(), 14),       Some(23));
-        assert_eq!(first_marker(&"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".to_string(), 14),  Some(29));
-        assert_eq!(first_marker(&"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".to_string(), 14),   Some(26));
+        assert_eq!(first_marker(EXAMPLE, 14),                              Some(19));
+        assert_eq!(first_marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 14),       Some(23));
+        assert_eq!(first_marker("nppdvjthqldpwncqszvftbrmjlhg", 14),       Some(23));
+        assert_eq!(first_marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14),  Some(29));
+        assert_eq!(first_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14),   Some(26));
+    }
+
+    #[test]
+    fn short_signal() {
+        assert_eq!(first_marker("abc", 4), None);
+        assert_eq!(read_signal("\nabC").unwrap_err().column, 3);
     }
 }
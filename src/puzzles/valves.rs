@@ -1,25 +1,48 @@
 use std::collections::HashMap;
-
-type FlowMap = HashMap<String, u32>;
-type PathMap = HashMap<String, Vec<String>>;
-
-fn read_valves(input: &String) -> (FlowMap, PathMap) {
+use crate::error::ParseError;
+use crate::parsing::lines;
+use crate::puzzles::Example;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 16;
+
+pub const EXAMPLE: &str = r"Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+pub type FlowMap = HashMap<String, u32>;
+pub type PathMap = HashMap<String, Vec<String>>;
+
+pub fn read_valves(input: &str) -> Result<(FlowMap, PathMap), ParseError> {
     let mut flow_map: FlowMap = HashMap::new();
     let mut path_map: PathMap = HashMap::new();
 
-    input.trim().lines().for_each(|line| {
-        let comma_stripped: String = line.trim().chars().filter(|c| *c!=',').collect();
-        let words: Vec<&str> = comma_stripped.split(" ").collect();
+    for line in lines(DAY, input) {
+        let words: Vec<&str> = line.text.split_whitespace().map(|w| w.trim_end_matches(',')).collect();
         
-        let id = words[1].to_string();
+        let id = line.word(&words, 1, "a valve name")?.to_string();
         
-        let flow: u32 = words[4].chars().filter(|c| c.is_ascii_digit()).collect::<String>()
-        .parse().unwrap();
+        let rate = line.word(&words, 4, "a flow rate")?;
+        let flow: u32 = match rate.strip_prefix("rate=").and_then(|r| r.strip_suffix(';')) {
+            Some(value) => line.number(value)?,
+            None => return Err(line.error(rate, format!("expected a flow rate like `rate=13;`, found `{rate}`")))
+        };
         
         flow_map.insert(id.to_string(), flow);
 
-        for i in 9..words.len() {
-            let destination = words[i].to_string();
+        if words.len() <= 9 {
+            return Err(line.error(&line.text[line.text.len()..], "expected at least one tunnel"));
+        }
+
+        for word in words.iter().skip(9) {
+            let destination = word.to_string();
             if let Some(v) = path_map.get_mut(&id) {
                 v.push(destination.to_string());
             } else {
@@ -32,9 +55,17 @@ fn read_valves(input: &String) -> (FlowMap, PathMap) {
                 path_map.insert(destination.to_string(), vec![id.to_string()]);
             }
         }
-    });
+    }
 
-    (flow_map, path_map)
+    if !flow_map.contains_key("AA") {
+        return Err(ParseError::input(DAY, "there is no starting valve AA"));
+    }
+    // Opened valves are tracked as bits of a u64
+    if path_map.len() > 64 {
+        return Err(ParseError::input(DAY, format!("at most 64 valves are supported, found {}", path_map.len())));
+    }
+
+    Ok((flow_map, path_map))
 }
 
 fn graphify(path_map: &PathMap)  -> (Vec<Vec<u32>>, HashMap<String, usize>) {
@@ -76,14 +107,13 @@ fn floyd_warshall_roy(graph: Vec<Vec<u32>>) -> Vec<Vec<u32>> {
     distance
 }
 
-pub fn part1(input: &String) -> u32 {
-    let (flow, _) = magical_calculation(input, 30);
-    flow
+pub fn part1(input: &str) -> Result<u32, ParseError> {
+    let (flow, _) = magical_calculation(&read_valves(input)?, 30);
+    Ok(flow)
 }
 
-pub fn magical_calculation(input: &String, turns: u32) -> (u32, HashMap<u64, u32>) {
-    let (flows, paths) = read_valves(input);
-    let (graph, idx_map) = graphify(&paths);
+pub fn magical_calculation((flows, paths): &(FlowMap, PathMap), turns: u32) -> (u32, HashMap<u64, u32>) {
+    let (graph, idx_map) = graphify(paths);
     let distances = floyd_warshall_roy(graph);
 
     let scored_nodes: Vec<String> = flows.iter()
@@ -95,13 +125,17 @@ pub fn magical_calculation(input: &String, turns: u32) -> (u32, HashMap<u64, u32
 
     let mut state_flow: HashMap<u64, u32> = HashMap::new();
 
-    let flow = travelling_salesman(&flows, &mut state_flow, &scored_nodes, &distances, &idx_map, turns, 0, start_index, 0);
+    let flow = travelling_salesman(flows, &mut state_flow, &scored_nodes, &distances, &idx_map, turns, 0, start_index, 0);
 
     (flow, state_flow)
 }
 
-pub fn part2(input: &String) -> u32 {
-    let (_, memory) = magical_calculation(input, 26);
+pub fn part2(input: &str) -> Result<u32, ParseError> {
+    Ok(best_pair_flow(&read_valves(input)?))
+}
+
+fn best_pair_flow(valves: &(FlowMap, PathMap)) -> u32 {
+    let (_, memory) = magical_calculation(valves, 26);
 
     let max_flow = memory.iter()
         .fold(0, |max, (&state1, &flow1)| {
@@ -118,11 +152,12 @@ pub fn part2(input: &String) -> u32 {
     max_flow
 }
 
+#[allow(clippy::too_many_arguments)]
 fn travelling_salesman(
     flows: &FlowMap,
     memory: &mut HashMap<u64, u32>,
-    scored_nodes: &Vec<String>,
-    distances: &Vec<Vec<u32>>,
+    scored_nodes: &[String],
+    distances: &[Vec<u32>],
     idx_map: &HashMap<String, usize>,
     minutes: u32,
     flow: u32,
@@ -146,7 +181,7 @@ fn travelling_salesman(
 
         if 
             state & (1 << new_index) >= 1   // Already switched on
-            || current_minutes <= 0         // Can't reach in time
+            || current_minutes == 0         // Can't reach in time
         {
             // Don't care about this node
             continue;
@@ -165,42 +200,48 @@ fn travelling_salesman(
     max_flow
 }
 
+pub struct ProboscideaVolcanium;
+
+impl Solver for ProboscideaVolcanium {
+    type Parsed = (FlowMap, PathMap);
+
+    fn parse(&self, input: &str) -> Result<(FlowMap, PathMap), ParseError> {
+        read_valves(input)
+    }
+
+    fn part1(&self, valves: &(FlowMap, PathMap)) -> Answer {
+        let (flow, _) = magical_calculation(valves, 30);
+        flow.into()
+    }
+
+    fn part2(&self, valves: &(FlowMap, PathMap)) -> Answer {
+        best_pair_flow(valves).into()
+    }
+}
+
+pub fn example() -> Example {
+    Example::new(EXAMPLE, ProboscideaVolcanium, 1651, 1707)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn part_one_sample() {
-        let input = r"
-        Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
-        Valve BB has flow rate=13; tunnels lead to valves CC, AA
-        Valve CC has flow rate=2; tunnels lead to valves DD, BB
-        Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
-        Valve EE has flow rate=3; tunnels lead to valves FF, DD
-        Valve FF has flow rate=0; tunnels lead to valves EE, GG
-        Valve GG has flow rate=0; tunnels lead to valves FF, HH
-        Valve HH has flow rate=22; tunnel leads to valve GG
-        Valve II has flow rate=0; tunnels lead to valves AA, JJ
-        Valve JJ has flow rate=21; tunnel leads to valve II".to_string();
-
-        assert_eq!(part1(&input), 1651);
+        assert_eq!(part1(EXAMPLE), Ok(1651));
     }
 
     #[test]
     fn magic2() {
-        let input = r"
-        Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
-        Valve BB has flow rate=13; tunnels lead to valves CC, AA
-        Valve CC has flow rate=2; tunnels lead to valves DD, BB
-        Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
-        Valve EE has flow rate=3; tunnels lead to valves FF, DD
-        Valve FF has flow rate=0; tunnels lead to valves EE, GG
-        Valve GG has flow rate=0; tunnels lead to valves FF, HH
-        Valve HH has flow rate=22; tunnel leads to valve GG
-        Valve II has flow rate=0; tunnels lead to valves AA, JJ
-        Valve JJ has flow rate=21; tunnel leads to valve II".to_string();
-
-        assert_eq!(part2(&input), 1707);
+        assert_eq!(part2(EXAMPLE), Ok(1707));
+    }
+
+    #[test]
+    fn bad_flow_rate() {
+        let input = "Valve AA has flow rate=x; tunnels lead to valves DD, II, BB";
+        let error = read_valves(input).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 24));
     }
 
     #[test]
@@ -0,0 +1,252 @@
+use std::time::Duration;
+use crate::answers::{Answers, Check};
+use crate::error::Error;
+use crate::puzzles::Day;
+use crate::solver::{Answer, Solution};
+
+pub struct Run<'a> {
+    pub day: &'a Day,
+    pub outcome: Result<Solution, Error>
+}
+
+pub fn run<'a>(day: &'a Day, input: Result<String, Error>) -> Run<'a> {
+    let outcome = input.and_then(|text| day.solver.solve(&text).map_err(Error::from));
+    Run { day, outcome }
+}
+
+pub fn run_example(day: &Day) -> Run<'_> {
+    let example = &day.example;
+    let outcome = example.solver.solve(example.input).map_err(Error::from);
+    Run { day, outcome }
+}
+
+// Accepts `all`, a single day like `7`, or a range like `3..9` or `3..=9`
+pub fn select<'a>(days: &'a [Day], spec: &str) -> Result<Vec<&'a Day>, String> {
+    let spec = spec.trim();
+    let day_number = |s: &str| s.trim().parse::<u8>().map_err(|_| format!("`{s}` is not a day number"));
+
+    let (first, last) = if spec == "all" {
+        (u8::MIN, u8::MAX)
+    } else if let Some((start, end)) = spec.split_once("..=") {
+        (day_number(start)?, day_number(end)?)
+    } else if let Some((start, end)) = spec.split_once("..") {
+        let end = day_number(end)?.checked_sub(1).ok_or_else(|| format!("`{spec}` is an empty range"))?;
+        (day_number(start)?, end)
+    } else {
+        let n = day_number(spec)?;
+        (n, n)
+    };
+
+    let selected: Vec<&Day> = days.iter().filter(|d| (first..=last).contains(&d.number)).collect();
+    if selected.is_empty() {
+        return Err(format!("No entry for day {spec}"));
+    }
+    Ok(selected)
+}
+
+fn cell(answer: &Answer) -> String {
+    match answer {
+        Answer::Image(rows) => format!("[{} row image]", rows.len()),
+        a => a.to_string()
+    }
+}
+
+fn time(duration: Duration) -> String {
+    format!("{duration:.2?}")
+}
+
+pub fn table(runs: &[Run]) -> String {
+    let header = ["Day", "Title", "Part 1", "Part 2", "Parse", "Part 1", "Part 2", "Total"];
+    let mut rows: Vec<Vec<String>> = vec![header.iter().map(|h| h.to_string()).collect()];
+    let mut total = Duration::ZERO;
+
+    for run in runs {
+        let mut row = vec![run.day.number.to_string(), run.day.title.to_string()];
+        match &run.outcome {
+            Ok(solution) => {
+                let t = solution.timings;
+                total += t.total();
+                row.extend([cell(&solution.part1), cell(&solution.part2)]);
+                row.extend([t.parse, t.part1, t.part2, t.total()].map(time));
+            },
+            Err(_) => row.extend(["error", "error", "-", "-", "-", "-"].map(|s| s.to_string()))
+        }
+        rows.push(row);
+    }
+    let mut footer = vec![String::new(); header.len()];
+    footer[1] = "Total".to_string();
+    footer[header.len()-1] = time(total);
+    rows.push(footer);
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| rows.iter().map(|r| r[i].chars().count()).max().unwrap_or(0))
+        .collect();
+
+    let mut output: Vec<String> = rows.iter().map(|row| {
+        row.iter().zip(&widths).enumerate()
+            .map(|(i, (text, width))| if i == 1 { format!("{text:<width$}") } else { format!("{text:>width$}") })
+            .collect::<Vec<String>>().join("  ").trim_end().to_string()
+    }).collect();
+
+    // Pictures and failures don't fit in a cell, so they follow the table
+    for run in runs {
+        match &run.outcome {
+            Ok(solution) => {
+                for (part, answer) in [(1, &solution.part1), (2, &solution.part2)] {
+                    if let Answer::Image(_) = answer {
+                        output.push(format!("\nDay {} part {part}:{answer}", run.day.number));
+                    }
+                }
+            },
+            Err(e) => output.push(format!("\nDay {} failed: {e}", run.day.number))
+        }
+    }
+
+    output.join("\n")
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c)
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::Text(s) => json_string(s),
+        Answer::Image(rows) => format!("[{}]", rows.iter().map(|r| json_string(r)).collect::<Vec<String>>().join(",")),
+        Answer::Unsolved => "null".to_string()
+    }
+}
+
+// One object per day, with timings in nanoseconds
+pub fn json(runs: &[Run]) -> String {
+    let objects: Vec<String> = runs.iter().map(|run| {
+        let head = format!("\"day\":{},\"title\":{}", run.day.number, json_string(run.day.title));
+        match &run.outcome {
+            Ok(solution) => {
+                let t = solution.timings;
+                let timings = format!("{{\"parse_ns\":{},\"part1_ns\":{},\"part2_ns\":{},\"total_ns\":{}}}",
+                    t.parse.as_nanos(), t.part1.as_nanos(), t.part2.as_nanos(), t.total().as_nanos());
+                format!("{{{head},\"part1\":{},\"part2\":{},\"timings\":{timings},\"error\":null}}",
+                    json_answer(&solution.part1), json_answer(&solution.part2))
+            },
+            Err(e) => format!("{{{head},\"part1\":null,\"part2\":null,\"timings\":null,\"error\":{}}}",
+                json_string(&e.to_string()))
+        }
+    }).collect();
+
+    format!("[\n  {}\n]", objects.join(",\n  "))
+}
+
+fn show(answer: &Answer) -> String {
+    match answer {
+        Answer::Image(rows) => format!("\n    {}", rows.join("\n    ")),
+        a => a.to_string()
+    }
+}
+
+// One line per part; the flag is false if anything failed or errored
+pub fn verify(runs: &[Run], answers: &Answers) -> (String, bool) {
+    let mut output: Vec<String> = Vec::new();
+    let mut ok = true;
+
+    for run in runs {
+        let number = run.day.number;
+        let solution = match &run.outcome {
+            Ok(s) => s,
+            Err(e) => {
+                ok = false;
+                output.push(format!("Day {number:>2}: error: {e}"));
+                continue;
+            }
+        };
+        for (part, answer) in [(1, &solution.part1), (2, &solution.part2)] {
+            let line = match answers.check(number, part, answer) {
+                Check::Pass => format!("pass {}", cell(answer)),
+                Check::Missing => format!("missing (got {})", show(answer)),
+                Check::Fail { expected } => {
+                    ok = false;
+                    format!("FAIL expected {}, got {}", show(&expected), show(answer))
+                }
+            };
+            output.push(format!("Day {number:>2} part {part}: {line}"));
+        }
+    }
+
+    (output.join("\n"), ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::read_answers;
+    use crate::puzzles::registry;
+
+    #[test]
+    fn selecting_days() {
+        let days = registry();
+        let numbers = |spec: &str| select(&days, spec).map(|ds| ds.iter().map(|d| d.number).collect::<Vec<u8>>());
+
+        assert_eq!(numbers("4"), Ok(vec![4]));
+        assert_eq!(numbers("3..=5"), Ok(vec![3, 4, 5]));
+        assert_eq!(numbers("3..5"), Ok(vec![3, 4]));
+        assert_eq!(numbers("all").unwrap().len(), days.len());
+        assert!(numbers("0").is_err());
+        assert!(numbers("x").is_err());
+    }
+
+    #[test]
+    fn table_rows() {
+        let days = registry();
+        let runs = vec![run(&days[0], Ok("1000\n\n2000\n3000".to_string()))];
+        let output = table(&runs);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        let cells: Vec<&str> = lines[1].split_whitespace().collect();
+        assert_eq!(cells[..5], ["1", "Calorie", "Counting", "5000", "6000"]);
+        assert!(lines[2].trim_start().starts_with("Total"));
+    }
+
+    #[test]
+    fn verifying_runs() {
+        let days = registry();
+        let runs = vec![run(&days[0], Ok("1000\n\n2000\n3000".to_string()))];
+
+        let (output, ok) = verify(&runs, &read_answers("[day1]\npart1 = 5000").unwrap());
+        assert!(ok);
+        assert_eq!(output, "Day  1 part 1: pass 5000\nDay  1 part 2: missing (got 6000)");
+
+        let (output, ok) = verify(&runs, &read_answers("[day1]\npart1 = 5000\npart2 = 7000").unwrap());
+        assert!(!ok);
+        assert!(output.ends_with("part 2: FAIL expected 7000, got 6000"));
+    }
+
+    #[test]
+    fn json_objects() {
+        let days = registry();
+        let runs = vec![
+            run(&days[0], Ok("1000\n\n2000\n3000".to_string())),
+            run(&days[1], Ok("A Q".to_string()))
+        ];
+        let output = json(&runs);
+        let lines: Vec<&str> = output.lines().map(str::trim).collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("{\"day\":1,\"title\":\"Calorie Counting\",\"part1\":5000,\"part2\":6000,\"timings\":{\"parse_ns\":"));
+        assert!(lines[1].ends_with(",\"error\":null},"));
+        assert!(lines[2].starts_with("{\"day\":2,\"title\":\"Rock Paper Scissors\",\"part1\":null,\"part2\":null,\"timings\":null,\"error\":\"day 2, line 1"));
+        assert_eq!(json_answer(&Answer::Image(vec!["#.".to_string(), "\"\\".to_string()])), "[\"#.\",\"\\\"\\\\\"]");
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+use crate::error::ParseError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    Image(Vec<String>),
+    Unsolved
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Image(rows) => write!(f, "\n{}", rows.join("\n")),
+            Answer::Unsolved => write!(f, "no solution")
+        }
+    }
+}
+
+macro_rules! number_answer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::Number(value as i64)
+            }
+        })*
+    };
+}
+
+number_answer!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(value: Vec<String>) -> Self {
+        Answer::Image(value)
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Answer::Unsolved, |v| v.into())
+    }
+}
+
+// A day's puzzle: parse the input once, then answer both parts from it
+pub trait Solver {
+    type Parsed;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(&self, parsed: &Self::Parsed) -> Answer;
+    fn part2(&self, parsed: &Self::Parsed) -> Answer;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Solution {
+    pub part1: Answer,
+    pub part2: Answer,
+    pub timings: Timings
+}
+
+// Object-safe view of a Solver so days with different parsed types can share a registry
+pub trait Puzzle {
+    fn solve(&self, input: &str) -> Result<Solution, ParseError>;
+}
+
+impl<S: Solver> Puzzle for S {
+    fn solve(&self, input: &str) -> Result<Solution, ParseError> {
+        let start = Instant::now();
+        let parsed = self.parse(input)?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let part1 = self.part1(&parsed);
+        let part1_time = start.elapsed();
+
+        let start = Instant::now();
+        let part2 = self.part2(&parsed);
+        let part2_time = start.elapsed();
+
+        Ok(Solution {
+            part1,
+            part2,
+            timings: Timings { parse, part1: part1_time, part2: part2_time }
+        })
+    }
+}